@@ -2,9 +2,16 @@ use std::{collections::HashMap, str::FromStr};
 
 use fisco_bcos_service::{
     abi::ABI,
+    account::{generate_account, Account},
     config::Config,
-    create_config_with_file, create_web3_service_with_config,
+    create_config_with_file, create_config_with_profile, create_web3_service_with_config,
     ethabi::token::Token,
+    get_config_profiles,
+    hd_wallet::{derive_accounts, generate_mnemonic, MNEMONIC_ENV, MNEMONIC_PASSPHRASE_ENV},
+    keystore::{
+        create_account_from_file, encrypt_keystore_v3, encrypt_p12, encrypt_pkcs8_private_key_pem,
+        KeystoreKdf, ACCOUNT_PASSWORD_ENV,
+    },
     precompiled::{
         chain_governance_service::ChainGovernanceService, cns_service::CNSService,
         consensus_service::ConsensusService, contract_life_cycle_service::ContractLifeCycleService,
@@ -82,13 +89,198 @@ fn parse_contract_constructor_tokens(args: &Vec<String>, config: &Option<Config>
 }
 
 pub(crate) struct Cli {
+    config_path: Option<String>,
     config: Option<Config>,
     web3_service: Option<Web3Service>,
 }
 
+///
+/// 解析 `set_config` 的参数：`<config_path> [--profile <profile>]`。
+fn parse_set_config_args(args: &[String]) -> Result<(String, Option<String>), Web3ServiceError> {
+    valid_args_len(args.len(), 1)?;
+    match args.len() {
+        1 => Ok((args[0].clone(), None)),
+        3 if args[1] == "--profile" => Ok((args[0].clone(), Some(args[2].clone()))),
+        _ => Err(Web3ServiceError::CustomError {
+            message: "Usage: set_config <config_path> [--profile <profile>]".to_owned(),
+        }),
+    }
+}
+
+///
+/// 移除参数中的 `--force`，返回其余参数及是否允许覆盖已存在的文件。
+fn parse_force_flag(args: Vec<String>) -> (Vec<String>, bool) {
+    let force = args.iter().any(|arg| arg == "--force");
+    (
+        args.into_iter().filter(|arg| arg != "--force").collect(),
+        force,
+    )
+}
+
+///
+/// 写入私钥文件：unix 下文件权限为 0o600，文件已存在时须指定 force 才会覆盖。
+fn write_private_key_file(
+    path: &std::path::Path,
+    content: &[u8],
+    force: bool,
+) -> Result<(), Web3ServiceError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true);
+    if force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path).map_err(|error| match error.kind() {
+        std::io::ErrorKind::AlreadyExists => Web3ServiceError::CustomError {
+            message: format!(
+                "{:} already exists, add --force to overwrite it",
+                path.display()
+            ),
+        },
+        _ => Web3ServiceError::from(error),
+    })?;
+    // 覆盖已存在的文件时 mode 不生效，需单独收紧权限。
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    std::io::Write::write_all(&mut file, content)?;
+    Ok(())
+}
+
+fn get_account_password() -> Result<String, Web3ServiceError> {
+    std::env::var(ACCOUNT_PASSWORD_ENV).map_err(|_| Web3ServiceError::CustomError {
+        message: format!("Please set the password with {:}", ACCOUNT_PASSWORD_ENV),
+    })
+}
+
+///
+/// 按格式（`ec`、`pkcs8`、`encrypted_pkcs8`、`keystore` 或 `p12`）导出账户私钥，
+/// 加密格式的密码来自环境变量 `FISCO_BCOS_ACCOUNT_PASSWORD`。
+fn export_account_pem(
+    account: &Account,
+    sm_crypto: bool,
+    pem_format: &str,
+) -> Result<Vec<u8>, Web3ServiceError> {
+    let pem = match pem_format {
+        "ec" => account.to_ec_private_key_pem(sm_crypto),
+        "pkcs8" => account.to_pkcs8_private_key_pem(sm_crypto),
+        "encrypted_pkcs8" => {
+            encrypt_pkcs8_private_key_pem(account, &get_account_password()?, sm_crypto)?
+        }
+        "keystore" => {
+            encrypt_keystore_v3(account, &get_account_password()?, KeystoreKdf::default())?
+        }
+        "p12" => return Ok(encrypt_p12(account, &get_account_password()?, sm_crypto)?),
+        _ => {
+            return Err(Web3ServiceError::CustomError {
+                message: format!(
+                    "Unsupported format {:?}, expected ec, pkcs8, encrypted_pkcs8, keystore or p12",
+                    pem_format
+                ),
+            })
+        }
+    };
+    Ok(pem.into_bytes())
+}
+
+///
+/// 导出格式对应的账户文件扩展名。
+fn get_account_file_extension(pem_format: &str) -> &'static str {
+    match pem_format {
+        "keystore" => "json",
+        "p12" => "p12",
+        _ => "pem",
+    }
+}
+
+///
+/// 输出账户信息，指定 `output_path` 时将 pem 写入文件（参见 `write_private_key_file`），
+/// 否则直接输出 pem 内容（二进制的 `p12` 格式须指定 `output_path`）。
+fn echo_account(
+    account: &Account,
+    sm_crypto: bool,
+    pem_format: &str,
+    output_path: Option<&String>,
+    force: bool,
+) {
+    if output_path.is_none() && pem_format == "p12" {
+        println!("\nError: Please specify the output path for the p12 format\n");
+        return;
+    }
+    let pem = match export_account_pem(account, sm_crypto, pem_format) {
+        Ok(pem) => pem,
+        Err(error) => {
+            println!("\nError: {:?}\n", error);
+            return;
+        }
+    };
+    println!(
+        "\nAddress: {}\nPublic Key: {}",
+        account.get_address(),
+        account.get_public_key()
+    );
+    match output_path {
+        Some(output_path) => match write_private_key_file(output_path.as_ref(), &pem, force) {
+            Ok(_) => println!("Private Key: {}\n", output_path),
+            Err(error) => println!("\nError: {:?}\n", error),
+        },
+        None => println!("\n{}", String::from_utf8_lossy(&pem)),
+    }
+}
+
+///
+/// 根据环境变量 `FISCO_BCOS_MNEMONIC` 中的助记词（未设置时随机生成并输出）派生 count 个账户，
+/// 指定 `output_dir` 时将各账户按 `account_<index>.<pem|json|p12>`（扩展名由导出格式决定）写入该目录。
+fn echo_derived_accounts(
+    sm_crypto: bool,
+    count: &str,
+    output_dir: Option<&String>,
+    pem_format: &str,
+    force: bool,
+) -> Result<(), Web3ServiceError> {
+    let count = count
+        .parse::<u32>()
+        .map_err(|_| Web3ServiceError::CustomError {
+            message: format!("Invalid count {:?}", count),
+        })?;
+    let mnemonic = match std::env::var(MNEMONIC_ENV) {
+        Ok(mnemonic) => mnemonic,
+        Err(_) => {
+            let mnemonic = generate_mnemonic(12)?;
+            println!("\nMnemonic: {}", mnemonic);
+            mnemonic
+        }
+    };
+    let passphrase = std::env::var(MNEMONIC_PASSPHRASE_ENV).unwrap_or_default();
+    for (index, (path, account)) in derive_accounts(&mnemonic, &passphrase, 0, count, sm_crypto)?
+        .iter()
+        .enumerate()
+    {
+        println!("\n{} {}", path, account.get_address());
+        if let Some(output_dir) = output_dir {
+            let pem = export_account_pem(account, sm_crypto, pem_format)?;
+            let output_path = std::path::Path::new(output_dir).join(format!(
+                "account_{}.{}",
+                index,
+                get_account_file_extension(pem_format)
+            ));
+            write_private_key_file(&output_path, &pem, force)?;
+            println!("Private Key: {}", output_path.display());
+        }
+    }
+    println!();
+    Ok(())
+}
+
 impl Cli {
-    fn set_config(&mut self, config_path: &str) {
-        match create_config_with_file(config_path) {
+    fn set_config(&mut self, config_path: &str, profile: Option<&str>) {
+        let config_result = match profile {
+            Some(profile) => create_config_with_profile(config_path, profile),
+            None => create_config_with_file(config_path),
+        };
+        match config_result {
             Ok(config) => {
                 match create_web3_service_with_config(&config) {
                     Ok(web3_service) => {
@@ -96,12 +288,33 @@ impl Cli {
                     }
                     Err(error) => println!("\n Web3 Service initialize error: {:?}\n", error),
                 };
+                self.config_path = Some(config_path.to_owned());
                 self.config = Some(config);
             }
             Err(error) => println!("\n Config initialize error: {:?}\n", error),
         };
     }
 
+    fn use_profile(&mut self, profile: &str) {
+        let config_path = match &self.config_path {
+            Some(config_path) => config_path.clone(),
+            None => {
+                println!("\nError: Please initialize the environment with set_config first\n");
+                return;
+            }
+        };
+        match get_config_profiles(&config_path) {
+            Ok(profiles) if profiles.iter().any(|item| item == profile) => {
+                self.set_config(&config_path, Some(profile))
+            }
+            Ok(profiles) => println!(
+                "\nError: The profile {:?} is not found, available profiles: {:?}\n",
+                profile, profiles
+            ),
+            Err(error) => println!("\n Config initialize error: {:?}\n", error),
+        }
+    }
+
     async fn call_web3_service(&self, method: &str, args: &Vec<String>) {
         let args_length = args.len();
         let web3_service = self.web3_service.as_ref().unwrap();
@@ -644,7 +857,9 @@ impl Cli {
 
     fn echo_help(&self) {
         println!(
-            "\n1. Use set_config to initialize environment(e.g., set_config ./config/config.json)."
+            "\n1. Use set_config to initialize environment(e.g., set_config ./config/config.json \
+             [--profile dev]), and use_profile to switch to another profile of the config(e.g., \
+             use_profile prod)."
         );
         println!("2. Use the below APIs to interact with FISCO BCOS：\n");
         println!(
@@ -942,12 +1157,32 @@ impl Cli {
         );
         println!(
             "* sql                                                        Execute CRUD operations \
-             with SQL.\n"
+             with SQL."
+        );
+        println!(
+            "* generate_account                                           Generate a new account \
+             (e.g., generate_account false ./accounts/bob.pem \
+             [ec|pkcs8|encrypted_pkcs8|keystore|p12] [--force])."
+        );
+        println!(
+            "* export_account                                             Show the address and \
+             public key of an account, and export it in another pem format(e.g., export_account \
+             ./accounts/bob.pem false pkcs8 ./accounts/bob_pkcs8.pem [--force])."
+        );
+        println!(
+            "* generate_mnemonic                                          Generate a BIP39 \
+             mnemonic (e.g., generate_mnemonic 24)."
+        );
+        println!(
+            "* derive_accounts                                            Derive accounts from the \
+             mnemonic in FISCO_BCOS_MNEMONIC by m/44'/60'/0'/0/<index> (e.g., derive_accounts \
+             false 10 ./accounts [ec|pkcs8|encrypted_pkcs8|keystore|p12] [--force]).\n"
         );
     }
 
     pub(crate) fn new() -> Cli {
         Cli {
+            config_path: None,
             config: None,
             web3_service: None,
         }
@@ -970,11 +1205,66 @@ impl Cli {
         } else {
             vec![]
         };
+        // 写入私钥文件的命令支持 `--force`，用于覆盖已存在的文件。
+        let (args, force) = match method {
+            "generate_account" | "export_account" | "derive_accounts" => parse_force_flag(args),
+            _ => (args, false),
+        };
         let args_length = args.len();
         match method {
             "help" => self.echo_help(),
-            "set_config" => match valid_args_len(args_length, 1) {
-                Ok(_) => self.set_config(&args[0]),
+            "set_config" => match parse_set_config_args(&args) {
+                Ok((config_path, profile)) => self.set_config(&config_path, profile.as_deref()),
+                Err(error) => println!("\nError: {:?}\n", error),
+            },
+            "use_profile" => match valid_args_len(args_length, 1) {
+                Ok(_) => self.use_profile(&args[0]),
+                Err(error) => println!("\nError: {:?}\n", error),
+            },
+            "generate_account" => match valid_args_len(args_length, 1) {
+                Ok(_) => {
+                    let sm_crypto = convert_str_to_bool(&args[0]);
+                    let pem_format = args.get(2).map(|v| v.as_str()).unwrap_or("ec");
+                    match generate_account(sm_crypto) {
+                        Ok(account) => {
+                            echo_account(&account, sm_crypto, pem_format, args.get(1), force)
+                        }
+                        Err(error) => println!("\nError: {:?}\n", error),
+                    }
+                }
+                Err(error) => println!("\nError: {:?}\n", error),
+            },
+            "export_account" => match valid_args_len(args_length, 2) {
+                Ok(_) => {
+                    let sm_crypto = convert_str_to_bool(&args[1]);
+                    let pem_format = args.get(2).map(|v| v.as_str()).unwrap_or("ec");
+                    let password = std::env::var(ACCOUNT_PASSWORD_ENV).ok();
+                    match create_account_from_file(&args[0], password.as_deref(), sm_crypto) {
+                        Ok(account) => {
+                            echo_account(&account, sm_crypto, pem_format, args.get(3), force)
+                        }
+                        Err(error) => println!("\nError: {:?}\n", error),
+                    }
+                }
+                Err(error) => println!("\nError: {:?}\n", error),
+            },
+            "generate_mnemonic" => {
+                let word_count = args.first().and_then(|v| v.parse::<usize>().ok());
+                match generate_mnemonic(word_count.unwrap_or(12)) {
+                    Ok(mnemonic) => println!("\n{}\n", mnemonic),
+                    Err(error) => println!("\nError: {:?}\n", error),
+                }
+            }
+            "derive_accounts" => match valid_args_len(args_length, 2) {
+                Ok(_) => {
+                    let sm_crypto = convert_str_to_bool(&args[0]);
+                    let pem_format = args.get(3).map(|v| v.as_str()).unwrap_or("ec");
+                    if let Err(error) =
+                        echo_derived_accounts(sm_crypto, &args[1], args.get(2), pem_format, force)
+                    {
+                        println!("\nError: {:?}\n", error);
+                    }
+                }
                 Err(error) => println!("\nError: {:?}\n", error),
             },
             _ => {
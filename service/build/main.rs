@@ -1,3 +1,4 @@
+#[cfg(feature = "channel")]
 use std::{env, path::PathBuf};
 
 fn check_rustc_versions() {
@@ -7,6 +8,7 @@ fn check_rustc_versions() {
     }
 }
 
+#[cfg(feature = "channel")]
 fn try_load_tassl_from_env(target: &str) -> Option<PathBuf> {
     let lib_path = env::var_os("TASSL_LIB_PATH");
     if lib_path.is_none() {
@@ -45,11 +47,10 @@ fn try_load_tassl_from_env(target: &str) -> Option<PathBuf> {
         println!("cargo:rustc-link-lib={}=ssl", lib_kind);
         println!("cargo:rustc-link-lib={}=crypto", lib_kind);
     }
-    println!("cargo:include={}", include_path.display());
-    println!("cargo:lib={}", lib_path.display());
     Some(include_path)
 }
 
+#[cfg(feature = "channel")]
 pub fn get_cfgs(openssl_version: u64) -> Vec<&'static str> {
     let mut cfgs = vec![];
     if openssl_version >= 0x3_00_00_00_0 {
@@ -91,6 +92,7 @@ pub fn get_cfgs(openssl_version: u64) -> Vec<&'static str> {
     cfgs
 }
 
+#[cfg(feature = "channel")]
 fn parse_version(version: &str) -> u64 {
     assert!(version.starts_with("0x"));
     let version = &version[2..];
@@ -101,6 +103,7 @@ fn parse_version(version: &str) -> u64 {
     u64::from_str_radix(version, 16).unwrap()
 }
 
+#[cfg(feature = "channel")]
 fn validate_headers(include_path: &PathBuf) {
     let mut gcc = cc::Build::new();
     gcc.include(include_path);
@@ -136,14 +139,13 @@ Failed to find TASSL development headers.
     for enabled in &enabled {
         println!("cargo:rustc-cfg=osslconf=\"{}\"", enabled);
     }
-    println!("cargo:conf={}", enabled.join(","));
     for cfg in get_cfgs(openssl_version) {
         println!("cargo:rustc-cfg={}", cfg);
     }
 }
 
-fn main() {
-    check_rustc_versions();
+#[cfg(feature = "channel")]
+fn build_tassl() {
     let target = env::var("TARGET").unwrap();
     let include_path = match try_load_tassl_from_env(&target) {
         Some(v) => v,
@@ -155,3 +157,10 @@ fn main() {
     };
     validate_headers(&include_path);
 }
+
+fn main() {
+    check_rustc_versions();
+    // 只有 `channel` feature 需要 TASSL，未启用时无需编译及链接 TASSL。
+    #[cfg(feature = "channel")]
+    build_tassl();
+}
@@ -6,13 +6,10 @@ use ethabi::{
     param_type::{ParamType, Writer},
     token::{LenientTokenizer, Token, Tokenizer},
     Contract, Error as ETHError, Event, EventParam, Function, Log, LogParam, Param, RawLog,
-    Result as ETHResult,
 };
 use thiserror::Error;
-use wedpr_l_crypto_hash_sm3::WedprSm3;
-use wedpr_l_utils::traits::Hash;
 
-use crate::config::Contract as ContractConfig;
+use crate::{config::Contract as ContractConfig, sm_crypto::sm3_hash};
 
 #[derive(Error, Debug)]
 pub enum ABIError {
@@ -78,23 +75,22 @@ impl ABI {
         Ok(tokens)
     }
 
-    fn sm_short_signature(&self, name: &str, params: &[ParamType]) -> Vec<u8> {
+    fn sm_short_signature(&self, name: &str, params: &[ParamType]) -> Result<Vec<u8>, ABIError> {
         let types = params
             .iter()
             .map(Writer::write)
             .collect::<Vec<String>>()
             .join(",");
         let data: Vec<u8> = From::from(format!("{}({})", name, types).as_str());
-        let sm3_hash = WedprSm3::default();
-        sm3_hash.hash(&data)[..4].to_vec()
+        Ok(sm3_hash(&data).map_err(|message| ABIError::CustomError { message })?[..4].to_vec())
     }
 
-    fn encode_sm_input(&self, function: &Function, tokens: &Vec<Token>) -> ETHResult<Vec<u8>> {
+    fn encode_sm_input(&self, function: &Function, tokens: &[Token]) -> Result<Vec<u8>, ABIError> {
         let params: Vec<ParamType> = function.inputs.iter().map(|p| p.kind.clone()).collect();
         if !Token::types_check(tokens, &params) {
-            return Err(ETHError::InvalidData);
+            return Err(ETHError::InvalidData.into());
         }
-        let mut transaction_data = self.sm_short_signature(&function.name, &params);
+        let mut transaction_data = self.sm_short_signature(&function.name, &params)?;
         transaction_data.extend(eth_encode(tokens));
         Ok(transaction_data)
     }
@@ -119,7 +115,7 @@ impl ABI {
         }
     }
 
-    fn event_sm_signature(&self, event: &Event) -> H256 {
+    fn event_sm_signature(&self, event: &Event) -> Result<H256, ABIError> {
         let types = event
             .inputs
             .iter()
@@ -127,12 +123,11 @@ impl ABI {
             .collect::<Vec<String>>()
             .join(",");
         let data: Vec<u8> = From::from(format!("{}({})", event.name, types).as_str());
-        let sm3_hash = WedprSm3::default();
-        let hash = sm3_hash.hash(&data)[..32].to_vec();
-        H256::from_slice(&hash)
+        let hash = sm3_hash(&data).map_err(|message| ABIError::CustomError { message })?;
+        Ok(H256::from_slice(&hash[..32]))
     }
 
-    fn parse_sm_log(&self, event: &Event, log: RawLog) -> Result<Log, ETHError> {
+    fn parse_sm_log(&self, event: &Event, log: RawLog) -> Result<Log, ABIError> {
         let topics = log.topics;
         let data = log.data;
         let topics_len = topics.len();
@@ -142,8 +137,8 @@ impl ABI {
             0
         } else {
             let event_signature = topics.get(0).ok_or(ETHError::InvalidData)?;
-            if event_signature != &self.event_sm_signature(event) {
-                return Err(ETHError::InvalidData);
+            if event_signature != &self.event_sm_signature(event)? {
+                return Err(ETHError::InvalidData.into());
             }
             1
         };
@@ -158,7 +153,7 @@ impl ABI {
             .collect::<Vec<u8>>();
         let topic_tokens = eth_decode(&topic_types, &flat_topics)?;
         if topic_tokens.len() != topics_len - to_skip {
-            return Err(ETHError::InvalidData);
+            return Err(ETHError::InvalidData.into());
         }
         let topics_named_tokens = topic_params
             .into_iter()
@@ -281,7 +276,7 @@ impl ABI {
             Some(contract) => {
                 let function = contract.function(&function_name)?;
                 if self.sm_crypto {
-                    Ok(self.encode_sm_input(&function, &tokens)?)
+                    self.encode_sm_input(function, tokens)
                 } else {
                     Ok(function.encode_input(&tokens)?)
                 }
@@ -322,7 +317,7 @@ impl ABI {
             Some(contract) => {
                 let event = contract.event(event_name)?;
                 if self.sm_crypto {
-                    Ok(self.parse_sm_log(&event, raw_log.clone())?)
+                    self.parse_sm_log(event, raw_log.clone())
                 } else {
                     Ok(event.parse_log(raw_log.clone())?)
                 }
@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use crate::{account::AccountError, keystore::create_account_from_file, signer::Signer};
+
+///
+/// 账户目录中可加载的账户文件扩展名。
+const ACCOUNT_FILE_EXTENSIONS: &[&str] = &["pem", "json", "p12"];
+
+///
+/// 按别名管理多个签名者（账户、远程签名服务等），可在同一 `Service` 中为每次调用选择发送者。
+#[derive(Default)]
+pub struct AccountManager {
+    signers: RwLock<HashMap<String, Arc<dyn Signer + Send + Sync>>>,
+}
+
+impl AccountManager {
+    pub fn new() -> AccountManager {
+        AccountManager::default()
+    }
+
+    ///
+    /// 以 alias 为别名注册签名者，别名已存在时替换原有签名者。
+    pub fn register(&self, alias: &str, signer: Arc<dyn Signer + Send + Sync>) {
+        self.signers
+            .write()
+            .unwrap()
+            .insert(alias.to_owned(), signer);
+    }
+
+    pub fn remove(&self, alias: &str) -> Option<Arc<dyn Signer + Send + Sync>> {
+        self.signers.write().unwrap().remove(alias)
+    }
+
+    pub fn get(&self, alias: &str) -> Option<Arc<dyn Signer + Send + Sync>> {
+        self.signers.read().unwrap().get(alias).cloned()
+    }
+
+    ///
+    /// 根据账户地址（可带 `0x` 前缀，不区分大小写）查找签名者。
+    pub fn get_by_address(&self, address: &str) -> Option<Arc<dyn Signer + Send + Sync>> {
+        let address = address.trim_start_matches("0x").to_lowercase();
+        self.signers
+            .read()
+            .unwrap()
+            .values()
+            .find(|signer| hex::encode(signer.address()) == address)
+            .cloned()
+    }
+
+    ///
+    /// 已注册的别名，按字母序排列。
+    pub fn get_aliases(&self) -> Vec<String> {
+        let mut aliases: Vec<String> = self.signers.read().unwrap().keys().cloned().collect();
+        aliases.sort();
+        aliases
+    }
+
+    ///
+    /// 加载目录中的全部账户文件（`.pem`、`.json` 及 `.p12`，不递归子目录），以文件名（不含扩展名）
+    /// 为别名注册，返回加载的别名。加密的账户文件使用同一个 password 解密。
+    pub fn load_directory(
+        &self,
+        directory: &str,
+        password: Option<&str>,
+        sm_crypto: bool,
+    ) -> Result<Vec<String>, AccountError> {
+        let mut accounts = vec![];
+        for entry in fs::read_dir(Path::new(directory))? {
+            let path = entry?.path();
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default();
+            if !path.is_file() || !ACCOUNT_FILE_EXTENSIONS.contains(&extension) {
+                continue;
+            }
+            let alias = match path.file_stem().and_then(|file_stem| file_stem.to_str()) {
+                Some(alias) => alias.to_owned(),
+                None => continue,
+            };
+            let account = create_account_from_file(&path.to_string_lossy(), password, sm_crypto)
+                .map_err(|error| AccountError::CustomError {
+                    message: format!("Failed to load account {:?}: {:?}", path, error),
+                })?;
+            accounts.push((alias, account));
+        }
+        accounts.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut signers = self.signers.write().unwrap();
+        Ok(accounts
+            .into_iter()
+            .map(|(alias, account)| {
+                signers.insert(alias.clone(), Arc::new(account));
+                alias
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::account::generate_account;
+    use crate::account_manager::*;
+
+    #[test]
+    fn test_load_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let directory = temp_dir.path();
+        let alice = generate_account(false).unwrap();
+        let bob = generate_account(false).unwrap();
+        fs::write(
+            directory.join("alice.pem"),
+            alice.to_pkcs8_private_key_pem(false),
+        )
+        .unwrap();
+        fs::write(directory.join("bob.pem"), bob.to_ec_private_key_pem(false)).unwrap();
+        fs::write(directory.join("README.md"), "accounts").unwrap();
+
+        let account_manager = AccountManager::new();
+        let aliases = account_manager
+            .load_directory(&directory.to_string_lossy(), None, false)
+            .unwrap();
+        assert_eq!(vec!["alice", "bob"], aliases);
+        assert_eq!(aliases, account_manager.get_aliases());
+        assert_eq!(
+            alice.address,
+            account_manager.get("alice").unwrap().address()
+        );
+        assert_eq!(
+            bob.address,
+            account_manager
+                .get_by_address(&bob.get_address())
+                .unwrap()
+                .address()
+        );
+        assert!(account_manager.remove("alice").is_some());
+        assert!(account_manager.get("alice").is_none());
+
+        // 目录中的证书等非私钥 pem 文件返回错误，不会加载为账户。
+        fs::write(
+            directory.join("ca.pem"),
+            &include_bytes!("../../configs/testing/ca.crt")[..],
+        )
+        .unwrap();
+        let account_manager = AccountManager::new();
+        assert!(account_manager
+            .load_directory(&directory.to_string_lossy(), None, false)
+            .is_err());
+        assert!(account_manager.get_aliases().is_empty());
+    }
+}
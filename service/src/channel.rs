@@ -1,16 +1,21 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use serde_json::{json, Value as JSONValue};
 use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use uuid::Uuid;
+use zeroize::Zeroizing;
 
 use crate::{
-    config::Config,
-    tassl::{TASSLError, TASSL},
+    config::{read_pem, Config},
+    tassl::{stream::TASSLStream, TASSLError, TASSL},
 };
 
 #[derive(Error, Debug)]
 pub enum ChannelError {
+    #[error("std::io::Error")]
+    StdIOError(#[from] std::io::Error),
+
     #[error("tassl error")]
     TASSLError(#[from] TASSLError),
 
@@ -22,41 +27,127 @@ pub enum ChannelError {
 
     #[error("std::array::TryFromSliceError")]
     TryFromSliceError(#[from] std::array::TryFromSliceError),
+
+    #[error("channel connection is dead")]
+    DeadConnection { message: String },
+
+    #[error("channel custom error")]
+    CustomError { message: String },
 }
 
+// SDK 支持的 channel 协议版本范围，握手时与节点协商出双方都支持的最高版本。
+pub const MINIMUM_PROTOCOL_VERSION: i32 = 1;
+pub const MAXIMUM_PROTOCOL_VERSION: i32 = 3;
+const CLIENT_TYPE: &str = "rust-sdk";
+
 // 格式详情参见：
 // https://fisco-bcos-documentation.readthedocs.io/zh_CN/latest/docs/design/protocol_description.html#channelmessage-v2
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum MessageType {
     RpcRequest,
+    Heartbeat,
+    ClientHandshake,
     ClientRegisterEventLog,
     AMOPClientTopics,
+    TransactionNotify,
     BlockNotify,
     EventLogPush,
 }
 
 impl MessageType {
-    fn value(&self) -> i16 {
+    pub fn value(&self) -> i16 {
         match *self {
             MessageType::RpcRequest => 0x12_i16,
+            MessageType::Heartbeat => 0x13_i16,
+            MessageType::ClientHandshake => 0x14_i16,
             MessageType::ClientRegisterEventLog => 0x15_i16,
             MessageType::AMOPClientTopics => 0x32_i16,
+            MessageType::TransactionNotify => 0x1000_i16,
             MessageType::BlockNotify => 0x1001_i16,
             MessageType::EventLogPush => 0x1002_i16,
         }
     }
 }
 
+#[derive(Debug)]
+pub struct ChannelMessage {
+    pub message_type: i16,
+    pub seq: String,
+    pub result: i32,
+    pub data: Vec<u8>,
+}
+
+impl ChannelMessage {
+    pub fn parse_data(&self) -> Result<JSONValue, ChannelError> {
+        parse_channel_message_data(self.message_type, &self.data)
+    }
+}
+
+///
+/// 握手后协商出的协议版本及节点版本。
+#[derive(Debug, Clone)]
+pub struct ChannelProtocol {
+    pub version: i32,
+    pub node_version: String,
+}
+
+impl ChannelProtocol {
+    ///
+    /// 心跳消息的内容，协议版本 1 使用字符串 `0`，之后的版本使用 JSON。
+    pub fn get_heartbeat_data(&self) -> Vec<u8> {
+        if self.version <= 1 {
+            b"0".to_vec()
+        } else {
+            json!({"heartBeat": 0}).to_string().into_bytes()
+        }
+    }
+}
+
+pub fn generate_channel_message_seq() -> String {
+    Uuid::new_v4().to_string().replace("-", "")
+}
+
 pub fn pack_channel_message(data: &Vec<u8>, message_type: MessageType) -> Vec<u8> {
+    pack_channel_message_with_seq(data, message_type, &generate_channel_message_seq())
+}
+
+pub fn pack_channel_message_with_seq(data: &[u8], message_type: MessageType, seq: &str) -> Vec<u8> {
     let mut buffer: Vec<u8> = Vec::new();
     buffer.append(&mut Vec::from(((data.len() + 42) as u32).to_be_bytes()));
     buffer.append(&mut Vec::from(message_type.value().to_be_bytes()));
-    buffer.append(&mut Uuid::new_v4().to_string().replace("-", "").into_bytes());
+    buffer.append(&mut Vec::from(seq.as_bytes()));
     buffer.append(&mut Vec::from(0_i32.to_be_bytes()));
-    buffer.append(&mut data.clone());
+    buffer.extend_from_slice(data);
     buffer
 }
 
+///
+/// 从 buffer 头部解析出一条完整的 channel 消息，并返回该消息所占的字节数。
+/// buffer 中的数据不足一条消息时返回 None。
+pub fn unpack_channel_message(
+    buffer: &[u8],
+) -> Result<Option<(ChannelMessage, usize)>, ChannelError> {
+    if buffer.len() < 4 {
+        return Ok(None);
+    }
+    let length = u32::from_be_bytes(buffer[0..4].try_into()?) as usize;
+    if length < 42 {
+        return Err(ChannelError::CustomError {
+            message: format!("Invalid channel message length: {:?}", length),
+        });
+    }
+    if buffer.len() < length {
+        return Ok(None);
+    }
+    let message = ChannelMessage {
+        message_type: i16::from_be_bytes(buffer[4..6].try_into()?),
+        seq: std::str::from_utf8(&buffer[6..38])?.to_owned(),
+        result: i32::from_be_bytes(buffer[38..42].try_into()?),
+        data: Vec::from(&buffer[42..length]),
+    };
+    Ok(Some((message, length)))
+}
+
 pub fn pack_amop_message(topic: &Vec<u8>, data: &Vec<u8>) -> Vec<u8> {
     let mut buffer: Vec<u8> = vec![];
     buffer.append(&mut Vec::from((1 + topic.len() as u8).to_be_bytes()));
@@ -65,20 +156,151 @@ pub fn pack_amop_message(topic: &Vec<u8>, data: &Vec<u8>) -> Vec<u8> {
     buffer
 }
 
-pub fn open_tassl(config: &Config) -> Result<TASSL, TASSLError> {
+fn read_optional_pem<T: AsRef<[u8]>>(
+    pem: &Option<T>,
+    file_path: &str,
+) -> Result<Zeroizing<Vec<u8>>, TASSLError> {
+    if pem.is_none() && file_path.is_empty() {
+        Ok(Zeroizing::new(vec![]))
+    } else {
+        Ok(read_pem(pem, file_path)?)
+    }
+}
+
+fn create_tassl(config: &Config) -> Result<TASSL, TASSLError> {
     let tassl = TASSL::new(config.timeout_seconds);
     tassl.init();
-    tassl.load_auth_files(
-        &config.authentication.ca_cert,
-        &config.authentication.sign_key,
-        &config.authentication.sign_cert,
-        &config.authentication.enc_key,
-        &config.authentication.enc_cert,
-    )?;
+    let authentication = &config.authentication;
+    if authentication.has_pem_bytes() {
+        tassl.load_auth_bytes(
+            &read_pem(&authentication.ca_cert_pem, &authentication.ca_cert)?,
+            &read_pem(&authentication.sign_key_pem, &authentication.sign_key)?,
+            &read_pem(&authentication.sign_cert_pem, &authentication.sign_cert)?,
+            &read_optional_pem(&authentication.enc_key_pem, &authentication.enc_key)?,
+            &read_optional_pem(&authentication.enc_cert_pem, &authentication.enc_cert)?,
+        )?;
+    } else {
+        tassl.load_auth_files(
+            &authentication.ca_cert,
+            &authentication.sign_key,
+            &authentication.sign_cert,
+            &authentication.enc_key,
+            &authentication.enc_cert,
+        )?;
+    }
+    Ok(tassl)
+}
+
+pub fn open_tassl(config: &Config) -> Result<TASSL, TASSLError> {
+    let tassl = create_tassl(config)?;
     tassl.connect(&config.node.host, config.node.port)?;
     Ok(tassl)
 }
 
+pub async fn open_tassl_stream(config: &Config) -> Result<TASSLStream, TASSLError> {
+    let tassl = create_tassl(config)?;
+    TASSLStream::connect(tassl, &config.node.host, config.node.port).await
+}
+
+///
+/// 建立 channel 连接并完成握手。
+pub async fn open_channel_stream(
+    config: &Config,
+) -> Result<(TASSLStream, ChannelProtocol), ChannelError> {
+    let mut stream = open_tassl_stream(config).await?;
+    let protocol = channel_handshake(&mut stream, config.timeout_seconds).await?;
+    Ok((stream, protocol))
+}
+
+fn parse_handshake_response(response: &JSONValue) -> Result<ChannelProtocol, ChannelError> {
+    let version = match response["protocol"].as_i64() {
+        Some(version) => version as i32,
+        None => {
+            return Err(ChannelError::CustomError {
+                message: format!("Invalid channel handshake response: {:?}", response),
+            })
+        }
+    };
+    if !(MINIMUM_PROTOCOL_VERSION..=MAXIMUM_PROTOCOL_VERSION).contains(&version) {
+        return Err(ChannelError::CustomError {
+            message: format!(
+                "Unsupported channel protocol version: {:?}, supported: [{:?}, {:?}]",
+                version, MINIMUM_PROTOCOL_VERSION, MAXIMUM_PROTOCOL_VERSION
+            ),
+        });
+    }
+    Ok(ChannelProtocol {
+        version,
+        node_version: response["nodeVersion"].as_str().unwrap_or("").to_owned(),
+    })
+}
+
+///
+/// 发送 `ClientHandshake` 消息协商协议版本，须在连接建立后、发送其他消息前调用。
+pub async fn channel_handshake<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    timeout_seconds: i64,
+) -> Result<ChannelProtocol, ChannelError> {
+    let seq = generate_channel_message_seq();
+    let data = json!({
+        "minimumSupport": MINIMUM_PROTOCOL_VERSION,
+        "maximumSupport": MAXIMUM_PROTOCOL_VERSION,
+        "clientType": CLIENT_TYPE,
+    });
+    let message = pack_channel_message_with_seq(
+        &serde_json::to_vec(&data)?,
+        MessageType::ClientHandshake,
+        &seq,
+    );
+    stream.write_all(&message).await?;
+    let read_response = async {
+        loop {
+            let message = read_channel_message(stream).await?;
+            if message.seq.eq(&seq) {
+                return Ok::<ChannelMessage, ChannelError>(message);
+            }
+        }
+    };
+    let timeout = Duration::from_secs(timeout_seconds as u64);
+    let message = match tokio::time::timeout(timeout, read_response).await {
+        Ok(result) => result?,
+        Err(_) => {
+            return Err(ChannelError::CustomError {
+                message: "The channel handshake timed out".to_owned(),
+            })
+        }
+    };
+    if message.result != 0 {
+        return Err(ChannelError::CustomError {
+            message: format!("The channel handshake failed, result: {:?}", message.result),
+        });
+    }
+    parse_handshake_response(&message.parse_data()?)
+}
+
+///
+/// 从异步流中读取一条完整的 channel 消息。
+pub async fn read_channel_message<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<ChannelMessage, ChannelError> {
+    let mut buffer: Vec<u8> = vec![0; 4];
+    reader.read_exact(&mut buffer).await?;
+    let buffer_size = u32::from_be_bytes(buffer.as_slice().try_into()?) as usize;
+    if buffer_size < 42 {
+        return Err(ChannelError::CustomError {
+            message: format!("Invalid channel message length: {:?}", buffer_size),
+        });
+    }
+    buffer.resize(buffer_size, 0);
+    reader.read_exact(&mut buffer[4..]).await?;
+    match unpack_channel_message(&buffer)? {
+        Some((message, _)) => Ok(message),
+        None => Err(ChannelError::CustomError {
+            message: "Incomplete channel message".to_owned(),
+        }),
+    }
+}
+
 pub fn channel_read(tassl: &TASSL) -> Result<JSONValue, ChannelError> {
     let mut buffer: Vec<u8> = vec![0; 4];
     tassl.read(&mut buffer[0..])?;
@@ -86,7 +308,11 @@ pub fn channel_read(tassl: &TASSL) -> Result<JSONValue, ChannelError> {
     buffer.append(&mut vec![0; buffer_size - 4]);
     tassl.read(&mut buffer[4..])?;
     let message_type = i16::from_be_bytes((&buffer[4..6]).try_into()?);
-    let data = Vec::from(&buffer[42..buffer_size]);
+    parse_channel_message_data(message_type, &buffer[42..buffer_size])
+}
+
+fn parse_channel_message_data(message_type: i16, data: &[u8]) -> Result<JSONValue, ChannelError> {
+    let data = Vec::from(data);
     if message_type == MessageType::BlockNotify.value() {
         Ok(parse_block_notify(data))
     } else if message_type == MessageType::ClientRegisterEventLog.value() {
@@ -119,3 +345,68 @@ fn parse_client_register_event_log(buffer: Vec<u8>) -> Result<JSONValue, Channel
         std::str::from_utf8(&buffer[topic_len..])?.trim_end_matches("\n"),
     )?)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::channel::*;
+
+    #[test]
+    fn test_unpack_channel_message() {
+        let seq = generate_channel_message_seq();
+        let data = br#"{"id":1,"jsonrpc":"2.0","result":"0x1"}"#.to_vec();
+        let mut buffer = pack_channel_message_with_seq(&data, MessageType::RpcRequest, &seq);
+        let length = buffer.len();
+        assert!(unpack_channel_message(&buffer[..length - 1])
+            .unwrap()
+            .is_none());
+
+        buffer.extend_from_slice(&[0, 0, 0]);
+        let (message, size) = unpack_channel_message(&buffer).unwrap().unwrap();
+        assert_eq!(length, size);
+        assert_eq!(MessageType::RpcRequest.value(), message.message_type);
+        assert_eq!(seq, message.seq);
+        assert_eq!(0, message.result);
+        assert_eq!(data, message.data);
+        assert_eq!(json!("0x1"), message.parse_data().unwrap()["result"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_channel_message() {
+        let first = pack_channel_message(&b"[\"first\"]".to_vec(), MessageType::RpcRequest);
+        let second = pack_channel_message(&b"[\"second\"]".to_vec(), MessageType::RpcRequest);
+        let buffer = [first, second].concat();
+        let mut reader = buffer.as_slice();
+        let message = read_channel_message(&mut reader).await.unwrap();
+        assert_eq!(json!(["first"]), message.parse_data().unwrap());
+        let message = read_channel_message(&mut reader).await.unwrap();
+        assert_eq!(json!(["second"]), message.parse_data().unwrap());
+        assert!(read_channel_message(&mut reader).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_channel_handshake() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        let handle = tokio::spawn(async move {
+            let request = read_channel_message(&mut server).await.unwrap();
+            assert_eq!(MessageType::ClientHandshake.value(), request.message_type);
+            let data = request.parse_data().unwrap();
+            assert_eq!(json!(MAXIMUM_PROTOCOL_VERSION), data["maximumSupport"]);
+            let notify = pack_channel_message(&b"[]".to_vec(), MessageType::BlockNotify);
+            server.write_all(&notify).await.unwrap();
+            let response = br#"{"protocol":2,"nodeVersion":"2.9.0"}"#;
+            let response =
+                pack_channel_message_with_seq(response, MessageType::ClientHandshake, &request.seq);
+            server.write_all(&response).await.unwrap();
+        });
+        let protocol = channel_handshake(&mut client, 1).await.unwrap();
+        handle.await.unwrap();
+        assert_eq!(2, protocol.version);
+        assert_eq!("2.9.0", protocol.node_version);
+        assert_eq!(
+            br#"{"heartBeat":0}"#.to_vec(),
+            protocol.get_heartbeat_data()
+        );
+
+        assert!(parse_handshake_response(&json!({"protocol": 4})).is_err());
+    }
+}
@@ -0,0 +1,497 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde_json::Value as JSONValue;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf},
+    sync::{mpsc, oneshot, Mutex as AsyncMutex, Notify},
+    task::JoinHandle,
+};
+
+use crate::{
+    channel::{
+        channel_handshake, generate_channel_message_seq, open_tassl_stream,
+        pack_channel_message_with_seq, read_channel_message, ChannelError, ChannelMessage,
+        ChannelProtocol, MessageType,
+    },
+    config::Config,
+};
+
+type PendingRequests = Mutex<HashMap<String, oneshot::Sender<Result<JSONValue, ChannelError>>>>;
+
+fn parse_transaction_notify(message: &ChannelMessage) -> Result<JSONValue, ChannelError> {
+    if message.result != 0 {
+        return Err(ChannelError::CustomError {
+            message: format!(
+                "The transaction notify returned an error, result: {:?}",
+                message.result
+            ),
+        });
+    }
+    message.parse_data()
+}
+
+fn dispatch_message(
+    state: &ConnectionState,
+    push_sender: &Option<mpsc::UnboundedSender<ChannelMessage>>,
+    message: ChannelMessage,
+) {
+    if message.message_type == MessageType::TransactionNotify.value() {
+        let sender = state.pending_notifies.lock().unwrap().remove(&message.seq);
+        if let Some(sender) = sender {
+            let _ = sender.send(parse_transaction_notify(&message));
+            return;
+        }
+    }
+    let sender = state.pending_requests.lock().unwrap().remove(&message.seq);
+    match sender {
+        Some(sender) => {
+            let _ = sender.send(message.parse_data());
+        }
+        None => {
+            if let Some(push_sender) = push_sender {
+                let _ = push_sender.send(message);
+            }
+        }
+    }
+}
+
+///
+/// 连接状态，连接关闭后所有等待中的请求都会收到 `ChannelError::DeadConnection`。
+struct ConnectionState {
+    pending_requests: PendingRequests,
+    pending_notifies: PendingRequests,
+    alive: AtomicBool,
+    closed: Notify,
+}
+
+impl ConnectionState {
+    fn close(&self, message: &str) {
+        self.alive.store(false, Ordering::SeqCst);
+        self.closed.notify_one();
+        for pending in [&self.pending_requests, &self.pending_notifies] {
+            for (_, sender) in pending.lock().unwrap().drain() {
+                let _ = sender.send(Err(ChannelError::DeadConnection {
+                    message: message.to_owned(),
+                }));
+            }
+        }
+    }
+}
+
+async fn run_read_loop<S: AsyncRead + AsyncWrite>(
+    mut reader: ReadHalf<S>,
+    state: Arc<ConnectionState>,
+    push_sender: Option<mpsc::UnboundedSender<ChannelMessage>>,
+) {
+    loop {
+        tokio::select! {
+            result = read_channel_message(&mut reader) => match result {
+                Ok(message) => dispatch_message(&state, &push_sender, message),
+                Err(err) => {
+                    state.close(&format!("The channel connection was broken: {:?}", err));
+                    return;
+                }
+            },
+            _ = state.closed.notified() => return,
+        }
+    }
+}
+
+async fn run_write_loop<S: AsyncRead + AsyncWrite>(
+    mut writer: WriteHalf<S>,
+    mut receiver: mpsc::UnboundedReceiver<Vec<u8>>,
+    state: Arc<ConnectionState>,
+) {
+    while let Some(message) = receiver.recv().await {
+        if let Err(err) = writer.write_all(&message).await {
+            state.close(&format!("The channel connection was broken: {:?}", err));
+            return;
+        }
+    }
+}
+
+async fn send_request(
+    sender: &mpsc::UnboundedSender<Vec<u8>>,
+    state: &ConnectionState,
+    data: &[u8],
+    message_type: MessageType,
+    timeout: Duration,
+) -> Result<JSONValue, ChannelError> {
+    send_request_with_seq(
+        sender,
+        state,
+        data,
+        message_type,
+        timeout,
+        &generate_channel_message_seq(),
+    )
+    .await
+}
+
+async fn send_request_with_seq(
+    sender: &mpsc::UnboundedSender<Vec<u8>>,
+    state: &ConnectionState,
+    data: &[u8],
+    message_type: MessageType,
+    timeout: Duration,
+    seq: &str,
+) -> Result<JSONValue, ChannelError> {
+    let (response_sender, response_receiver) = oneshot::channel();
+    state
+        .pending_requests
+        .lock()
+        .unwrap()
+        .insert(seq.to_owned(), response_sender);
+    let message = pack_channel_message_with_seq(data, message_type, seq);
+    if sender.send(message).is_err() {
+        state.pending_requests.lock().unwrap().remove(seq);
+        return Err(ChannelError::DeadConnection {
+            message: "The channel connection was closed".to_owned(),
+        });
+    }
+    match tokio::time::timeout(timeout, response_receiver).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err(ChannelError::DeadConnection {
+            message: "The channel connection was closed".to_owned(),
+        }),
+        Err(_) => {
+            state.pending_requests.lock().unwrap().remove(seq);
+            Err(ChannelError::CustomError {
+                message: format!("The channel request timed out, seq: {:?}", seq),
+            })
+        }
+    }
+}
+
+///
+/// 定时发送心跳，超时未收到响应时认为连接已失效并关闭连接。
+async fn run_heartbeat_loop(
+    sender: mpsc::UnboundedSender<Vec<u8>>,
+    state: Arc<ConnectionState>,
+    heartbeat_data: Vec<u8>,
+    interval: Duration,
+    timeout: Duration,
+) {
+    loop {
+        tokio::time::sleep(interval).await;
+        if !state.alive.load(Ordering::SeqCst) {
+            return;
+        }
+        let result = send_request(
+            &sender,
+            &state,
+            &heartbeat_data,
+            MessageType::Heartbeat,
+            timeout,
+        )
+        .await;
+        if let Err(err) = result {
+            state.close(&format!("The channel heartbeat failed: {:?}", err));
+            return;
+        }
+    }
+}
+
+///
+/// 等待节点推送交易回执（`TransactionNotify`）的 receiver，drop 时会取消等待。
+pub struct TransactionNotifyReceiver {
+    seq: String,
+    state: Arc<ConnectionState>,
+    receiver: Option<oneshot::Receiver<Result<JSONValue, ChannelError>>>,
+}
+
+impl TransactionNotifyReceiver {
+    ///
+    /// 等待交易回执，超时返回 None。
+    pub async fn receive(mut self, timeout: Duration) -> Result<Option<JSONValue>, ChannelError> {
+        let receiver = match self.receiver.take() {
+            Some(receiver) => receiver,
+            None => return Ok(None),
+        };
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(result)) => result.map(Some),
+            Ok(Err(_)) => Err(ChannelError::DeadConnection {
+                message: "The channel connection was closed".to_owned(),
+            }),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl Drop for TransactionNotifyReceiver {
+    fn drop(&mut self) {
+        self.state
+            .pending_notifies
+            .lock()
+            .unwrap()
+            .remove(&self.seq);
+    }
+}
+
+///
+/// 长连接的 channel 连接，同一连接上可同时发送多个请求，响应通过消息中的 seq 与请求对应。
+/// 连接建立后会先进行握手协商协议版本，之后按 `channel_heartbeat_interval_seconds` 定时发送心跳。
+pub struct ChannelConnection {
+    sender: mpsc::UnboundedSender<Vec<u8>>,
+    state: Arc<ConnectionState>,
+    protocol: ChannelProtocol,
+    tasks: Vec<JoinHandle<()>>,
+    timeout_seconds: i64,
+}
+
+impl ChannelConnection {
+    async fn create(
+        config: &Config,
+        push_sender: Option<mpsc::UnboundedSender<ChannelMessage>>,
+    ) -> Result<ChannelConnection, ChannelError> {
+        let stream = open_tassl_stream(config).await?;
+        ChannelConnection::create_with_stream(stream, config, push_sender).await
+    }
+
+    ///
+    /// 在已建立的连接（如测试中的内存连接）上完成握手并创建 channel 连接。
+    pub(crate) async fn create_with_stream<S>(
+        mut stream: S,
+        config: &Config,
+        push_sender: Option<mpsc::UnboundedSender<ChannelMessage>>,
+    ) -> Result<ChannelConnection, ChannelError>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let protocol = channel_handshake(&mut stream, config.timeout_seconds).await?;
+        let (reader, writer) = tokio::io::split(stream);
+        let (sender, receiver) = mpsc::unbounded_channel::<Vec<u8>>();
+        let state = Arc::new(ConnectionState {
+            pending_requests: Mutex::new(HashMap::new()),
+            pending_notifies: Mutex::new(HashMap::new()),
+            alive: AtomicBool::new(true),
+            closed: Notify::new(),
+        });
+        let mut tasks = vec![
+            tokio::spawn(run_read_loop(reader, state.clone(), push_sender)),
+            tokio::spawn(run_write_loop(writer, receiver, state.clone())),
+        ];
+        if config.channel_heartbeat_interval_seconds > 0 {
+            tasks.push(tokio::spawn(run_heartbeat_loop(
+                sender.clone(),
+                state.clone(),
+                protocol.get_heartbeat_data(),
+                Duration::from_secs(config.channel_heartbeat_interval_seconds),
+                Duration::from_secs(config.timeout_seconds as u64),
+            )));
+        }
+        Ok(ChannelConnection {
+            sender,
+            state,
+            protocol,
+            tasks,
+            timeout_seconds: config.timeout_seconds,
+        })
+    }
+
+    pub async fn open(config: &Config) -> Result<ChannelConnection, ChannelError> {
+        ChannelConnection::create(config, None).await
+    }
+
+    ///
+    /// 建立连接，并返回接收节点主动推送消息（seq 与请求不对应的消息）的 receiver。
+    /// 连接失效后 receiver 会被关闭。
+    pub async fn open_with_push_receiver(
+        config: &Config,
+    ) -> Result<(ChannelConnection, mpsc::UnboundedReceiver<ChannelMessage>), ChannelError> {
+        let (push_sender, push_receiver) = mpsc::unbounded_channel::<ChannelMessage>();
+        let connection = ChannelConnection::create(config, Some(push_sender)).await?;
+        Ok((connection, push_receiver))
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.state.alive.load(Ordering::SeqCst)
+    }
+
+    pub fn get_protocol(&self) -> &ChannelProtocol {
+        &self.protocol
+    }
+
+    ///
+    /// 发送消息，不等待响应。
+    pub fn send(&self, data: &[u8], message_type: MessageType) -> Result<(), ChannelError> {
+        let message =
+            pack_channel_message_with_seq(data, message_type, &generate_channel_message_seq());
+        self.sender
+            .send(message)
+            .map_err(|_| ChannelError::DeadConnection {
+                message: "The channel connection was closed".to_owned(),
+            })
+    }
+
+    pub async fn request(
+        &self,
+        data: &[u8],
+        message_type: MessageType,
+    ) -> Result<JSONValue, ChannelError> {
+        let timeout = Duration::from_secs(self.timeout_seconds as u64);
+        send_request(&self.sender, &self.state, data, message_type, timeout).await
+    }
+
+    ///
+    /// 发送请求，并返回等待节点以相同 seq 推送交易回执的 receiver，用于发送交易。
+    pub async fn request_with_notify(
+        &self,
+        data: &[u8],
+        message_type: MessageType,
+    ) -> Result<(JSONValue, TransactionNotifyReceiver), ChannelError> {
+        let seq = generate_channel_message_seq();
+        let (notify_sender, notify_receiver) = oneshot::channel();
+        self.state
+            .pending_notifies
+            .lock()
+            .unwrap()
+            .insert(seq.clone(), notify_sender);
+        let notify_receiver = TransactionNotifyReceiver {
+            seq: seq.clone(),
+            state: self.state.clone(),
+            receiver: Some(notify_receiver),
+        };
+        let timeout = Duration::from_secs(self.timeout_seconds as u64);
+        let response =
+            send_request_with_seq(&self.sender, &self.state, data, message_type, timeout, &seq)
+                .await?;
+        Ok((response, notify_receiver))
+    }
+}
+
+impl Drop for ChannelConnection {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+///
+/// channel 连接池，请求以轮询的方式分配到池中的各个连接上，断开的连接会在下次使用时重建。
+pub struct ChannelPool {
+    config: Config,
+    connections: Vec<AsyncMutex<Option<Arc<ChannelConnection>>>>,
+    cursor: AtomicUsize,
+}
+
+impl ChannelPool {
+    async fn get_connection(&self) -> Result<Arc<ChannelConnection>, ChannelError> {
+        let index = self.cursor.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        let mut connection = self.connections[index].lock().await;
+        if let Some(connection) = connection.as_ref() {
+            if connection.is_alive() {
+                return Ok(connection.clone());
+            }
+        }
+        let new_connection = Arc::new(ChannelConnection::open(&self.config).await?);
+        *connection = Some(new_connection.clone());
+        Ok(new_connection)
+    }
+
+    pub fn new(config: &Config) -> ChannelPool {
+        let pool_size = if config.channel_pool_size > 0 {
+            config.channel_pool_size
+        } else {
+            1
+        };
+        ChannelPool {
+            config: config.clone(),
+            connections: (0..pool_size).map(|_| AsyncMutex::new(None)).collect(),
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    ///
+    /// 只包含 connection 一个连接的连接池，连接断开后按 config 重新建立连接。
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn with_connection(config: &Config, connection: ChannelConnection) -> ChannelPool {
+        ChannelPool {
+            config: config.clone(),
+            connections: vec![AsyncMutex::new(Some(Arc::new(connection)))],
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    pub async fn request(
+        &self,
+        data: &[u8],
+        message_type: MessageType,
+    ) -> Result<JSONValue, ChannelError> {
+        let connection = self.get_connection().await?;
+        connection.request(data, message_type).await
+    }
+
+    pub async fn request_with_notify(
+        &self,
+        data: &[u8],
+        message_type: MessageType,
+    ) -> Result<(JSONValue, TransactionNotifyReceiver), ChannelError> {
+        let connection = self.get_connection().await?;
+        connection.request_with_notify(data, message_type).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::channel::{pack_channel_message_with_seq, unpack_channel_message};
+    use crate::channel_pool::*;
+
+    #[tokio::test]
+    async fn test_dispatch_transaction_notify() {
+        let state = Arc::new(ConnectionState {
+            pending_requests: Mutex::new(HashMap::new()),
+            pending_notifies: Mutex::new(HashMap::new()),
+            alive: AtomicBool::new(true),
+            closed: Notify::new(),
+        });
+        let seq = generate_channel_message_seq();
+        let (response_sender, response_receiver) = oneshot::channel();
+        let (notify_sender, notify_receiver) = oneshot::channel();
+        state
+            .pending_requests
+            .lock()
+            .unwrap()
+            .insert(seq.clone(), response_sender);
+        state
+            .pending_notifies
+            .lock()
+            .unwrap()
+            .insert(seq.clone(), notify_sender);
+        let notify_receiver = TransactionNotifyReceiver {
+            seq: seq.clone(),
+            state: state.clone(),
+            receiver: Some(notify_receiver),
+        };
+
+        let response = br#"{"id":1,"jsonrpc":"2.0","result":"0x1"}"#;
+        let receipt = br#"{"status":"0x0","transactionHash":"0x1"}"#;
+        for (data, message_type) in [
+            (&response[..], MessageType::RpcRequest),
+            (&receipt[..], MessageType::TransactionNotify),
+        ] {
+            let buffer = pack_channel_message_with_seq(data, message_type, &seq);
+            let (message, _) = unpack_channel_message(&buffer).unwrap().unwrap();
+            dispatch_message(&state, &None, message);
+        }
+        let response = response_receiver.await.unwrap().unwrap();
+        assert_eq!(json!("0x1"), response["result"]);
+        let receipt = notify_receiver
+            .receive(Duration::from_secs(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(json!("0x0"), receipt["status"]);
+        assert!(state.pending_notifies.lock().unwrap().is_empty());
+    }
+}
@@ -1,17 +1,71 @@
 use std::{
-    fs,
+    env, fmt, fs,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use serde::Deserialize;
+use serde_json::{Map as JSONMap, Value as JSONValue};
+use thiserror::Error;
+use zeroize::Zeroizing;
 
-#[derive(Deserialize, Clone, Debug)]
+use crate::{
+    account::is_sm_private_key,
+    keystore::{get_account_file_format, AccountFileFormat, DER_SEQUENCE},
+    signer::Signer,
+    sm_crypto::{is_sm_crypto_enabled, SM_CRYPTO_DISABLED_MESSAGE},
+};
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("std::io::Error")]
+    StdIOError(#[from] std::io::Error),
+
+    #[error("serde_json::Error")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("toml::de::Error")]
+    TomlError(#[from] toml::de::Error),
+
+    #[error("serde_yaml::Error")]
+    YamlError(#[from] serde_yaml::Error),
+
+    #[error("invalid config: {}", .problems.join("; "))]
+    InvalidConfig { problems: Vec<String> },
+
+    #[error("config custom error")]
+    CustomError { message: String },
+}
+
+///
+/// 环境变量覆盖配置时使用的前缀，如 `FISCO_BCOS_NODE_HOST` 对应 `node.host`。
+pub const ENV_PREFIX: &str = "FISCO_BCOS_";
+
+#[derive(Deserialize, Clone, Default, Debug)]
 pub struct Node {
     pub host: String,
     pub port: i32,
+    #[serde(default)]
+    pub priority: i32,
 }
 
-#[derive(Deserialize, Clone, Default, Debug)]
+///
+/// `Debug` 输出中用于替换私钥、密码等敏感内容。
+fn redact<T>(value: &Option<T>) -> Option<&'static str> {
+    value.as_ref().map(|_| "[REDACTED]")
+}
+
+///
+/// 与 `redact` 相同，用于未设置时为空字符串的配置项。
+fn redact_str(value: &str) -> &'static str {
+    if value.is_empty() {
+        ""
+    } else {
+        "[REDACTED]"
+    }
+}
+
+#[derive(Deserialize, Clone, Default)]
 pub struct Authentication {
     pub ca_cert: String,
     pub sign_cert: String,
@@ -20,12 +74,208 @@ pub struct Authentication {
     pub enc_key: String,
     #[serde(default)]
     pub enc_cert: String,
+    ///
+    /// 以下为内存中的 pem 内容（仅能通过 `ConfigBuilder` 设置），设置后优先于对应的文件路径。
+    #[serde(skip)]
+    pub ca_cert_pem: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub sign_cert_pem: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub sign_key_pem: Option<Zeroizing<Vec<u8>>>,
+    #[serde(skip)]
+    pub enc_key_pem: Option<Zeroizing<Vec<u8>>>,
+    #[serde(skip)]
+    pub enc_cert_pem: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for Authentication {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authentication")
+            .field("ca_cert", &self.ca_cert)
+            .field("sign_cert", &self.sign_cert)
+            .field("sign_key", &self.sign_key)
+            .field("enc_key", &self.enc_key)
+            .field("enc_cert", &self.enc_cert)
+            .field("ca_cert_pem", &self.ca_cert_pem)
+            .field("sign_cert_pem", &self.sign_cert_pem)
+            .field("sign_key_pem", &redact(&self.sign_key_pem))
+            .field("enc_key_pem", &redact(&self.enc_key_pem))
+            .field("enc_cert_pem", &self.enc_cert_pem)
+            .finish()
+    }
+}
+
+impl Authentication {
+    ///
+    /// 是否设置了内存中的 pem 内容。
+    pub fn has_pem_bytes(&self) -> bool {
+        self.ca_cert_pem.is_some()
+            || self.sign_cert_pem.is_some()
+            || self.sign_key_pem.is_some()
+            || self.enc_key_pem.is_some()
+            || self.enc_cert_pem.is_some()
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct RPCConfig {
+    #[serde(default = "default_rpc_scheme")]
+    pub scheme: String,
+    #[serde(default)]
+    pub ca_cert: String,
+    #[serde(default)]
+    pub client_cert: String,
+    #[serde(default)]
+    pub client_key: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub bearer_token: String,
+    #[serde(default)]
+    pub proxy: String,
+    ///
+    /// 以下为内存中的 pem 内容（仅能通过 `ConfigBuilder` 设置），设置后优先于对应的文件路径。
+    #[serde(skip)]
+    pub ca_cert_pem: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub client_cert_pem: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub client_key_pem: Option<Zeroizing<Vec<u8>>>,
+}
+
+impl fmt::Debug for RPCConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RPCConfig")
+            .field("scheme", &self.scheme)
+            .field("ca_cert", &self.ca_cert)
+            .field("client_cert", &self.client_cert)
+            .field("client_key", &self.client_key)
+            .field("username", &self.username)
+            .field("password", &redact_str(&self.password))
+            .field("bearer_token", &redact_str(&self.bearer_token))
+            .field("proxy", &self.proxy)
+            .field("ca_cert_pem", &self.ca_cert_pem)
+            .field("client_cert_pem", &self.client_cert_pem)
+            .field("client_key_pem", &redact(&self.client_key_pem))
+            .finish()
+    }
+}
+
+fn default_rpc_scheme() -> String {
+    String::from("http")
+}
+
+impl Default for RPCConfig {
+    fn default() -> RPCConfig {
+        RPCConfig {
+            scheme: default_rpc_scheme(),
+            ca_cert: String::default(),
+            client_cert: String::default(),
+            client_key: String::default(),
+            username: String::default(),
+            password: String::default(),
+            bearer_token: String::default(),
+            proxy: String::default(),
+            ca_cert_pem: None,
+            client_cert_pem: None,
+            client_key_pem: None,
+        }
+    }
+}
+
+///
+/// 远程签名服务配置（接口参见 `signer::RemoteSigner`），public_key 为签名服务所持私钥对应的公钥（十六进制）。
+#[derive(Deserialize, Clone, Default)]
+pub struct RemoteSignerConfig {
+    pub url: String,
+    pub public_key: String,
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+}
+
+impl fmt::Debug for RemoteSignerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSignerConfig")
+            .field("url", &self.url)
+            .field("public_key", &self.public_key)
+            .field("bearer_token", &redact(&self.bearer_token))
+            .finish()
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
+pub struct RetryPolicy {
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_retry_initial_backoff_milliseconds")]
+    pub initial_backoff_milliseconds: u64,
+    #[serde(default = "default_retry_max_backoff_milliseconds")]
+    pub max_backoff_milliseconds: u64,
+    #[serde(default = "default_retry_multiplier")]
+    pub multiplier: f64,
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: f64,
+    #[serde(default = "default_retryable_errors")]
+    pub retryable_errors: Vec<String>,
+    #[serde(default)]
+    pub retryable_codes: Vec<i32>,
+    #[serde(default)]
+    pub retry_transactions: bool,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_initial_backoff_milliseconds() -> u64 {
+    200
+}
+
+fn default_retry_max_backoff_milliseconds() -> u64 {
+    5000
+}
+
+fn default_retry_multiplier() -> f64 {
+    2.0
+}
+
+fn default_retry_jitter() -> f64 {
+    0.2
+}
+
+fn default_retryable_errors() -> Vec<String> {
+    vec![
+        String::from("StdIOError"),
+        String::from("HyperError"),
+        String::from("ChannelError"),
+        String::from("TASSLError"),
+    ]
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: default_retry_max_attempts(),
+            initial_backoff_milliseconds: default_retry_initial_backoff_milliseconds(),
+            max_backoff_milliseconds: default_retry_max_backoff_milliseconds(),
+            multiplier: default_retry_multiplier(),
+            jitter: default_retry_jitter(),
+            retryable_errors: default_retryable_errors(),
+            retryable_codes: vec![],
+            retry_transactions: false,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
 pub struct Contract {
+    #[serde(default)]
     pub solc: String,
+    #[serde(default)]
     pub source: String,
+    #[serde(default)]
     pub output: String,
 }
 
@@ -43,34 +293,191 @@ impl Contract {
     }
 }
 
-#[derive(Deserialize, Clone, Debug)]
+fn default_group_id() -> u32 {
+    1
+}
+
+fn default_chain_id() -> u32 {
+    1
+}
+
+fn default_timeout_seconds() -> i64 {
+    10
+}
+
+fn default_channel_pool_size() -> usize {
+    1
+}
+
+fn default_channel_heartbeat_interval_seconds() -> u64 {
+    30
+}
+
+fn default_load_balance_strategy() -> String {
+    String::from("round_robin")
+}
+
+fn default_node_probe_interval_seconds() -> u64 {
+    5
+}
+
+#[derive(Deserialize, Clone)]
 pub struct Config {
     pub service_type: String,
+    #[serde(default)]
     pub node: Node,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default = "default_load_balance_strategy")]
+    pub load_balance_strategy: String,
+    #[serde(default = "default_node_probe_interval_seconds")]
+    pub node_probe_interval_seconds: u64,
+    #[serde(default)]
     pub contract: Contract,
+    #[serde(default)]
     pub account: String,
+    ///
+    /// 内存中的账户私钥 pem 内容（仅能通过 `ConfigBuilder` 设置），设置后优先于 `account`。
+    #[serde(skip)]
+    pub account_pem: Option<Zeroizing<Vec<u8>>>,
+    ///
+    /// 加密账户文件（V3 JSON keystore、加密的 PKCS#8 pem 或 `.p12`）的密码。
+    #[serde(default)]
+    pub account_password: Option<String>,
+    ///
+    /// 远程签名服务，设置后使用该服务签名，不再需要 `account`。
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSignerConfig>,
+    ///
+    /// 账户目录，其中的账户文件会以文件名（不含扩展名）为别名加载到 `Service` 的账户管理器中，
+    /// 加密的账户文件使用 `account_password` 解密。
+    #[serde(default)]
+    pub accounts_directory: Option<String>,
+    ///
+    /// 自定义的签名者（仅能通过 `ConfigBuilder` 设置），设置后优先于 `remote_signer` 及 `account`。
+    #[serde(skip)]
+    pub signer: Option<Arc<dyn Signer + Send + Sync>>,
     #[serde(default)]
     pub authentication: Authentication,
+    #[serde(default)]
+    pub rpc: RPCConfig,
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    #[serde(default)]
     pub sm_crypto: bool,
+    #[serde(default = "default_group_id")]
     pub group_id: u32,
+    #[serde(default = "default_chain_id")]
     pub chain_id: u32,
+    #[serde(default = "default_timeout_seconds")]
     pub timeout_seconds: i64,
+    #[serde(default = "default_channel_pool_size")]
+    pub channel_pool_size: usize,
+    #[serde(default = "default_channel_heartbeat_interval_seconds")]
+    pub channel_heartbeat_interval_seconds: u64,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("service_type", &self.service_type)
+            .field("node", &self.node)
+            .field("nodes", &self.nodes)
+            .field("load_balance_strategy", &self.load_balance_strategy)
+            .field(
+                "node_probe_interval_seconds",
+                &self.node_probe_interval_seconds,
+            )
+            .field("contract", &self.contract)
+            .field("account", &self.account)
+            .field("account_pem", &redact(&self.account_pem))
+            .field("account_password", &redact(&self.account_password))
+            .field("remote_signer", &self.remote_signer)
+            .field("accounts_directory", &self.accounts_directory)
+            .field("signer", &self.signer)
+            .field("authentication", &self.authentication)
+            .field("rpc", &self.rpc)
+            .field("retry", &self.retry)
+            .field("sm_crypto", &self.sm_crypto)
+            .field("group_id", &self.group_id)
+            .field("chain_id", &self.chain_id)
+            .field("timeout_seconds", &self.timeout_seconds)
+            .field("channel_pool_size", &self.channel_pool_size)
+            .field(
+                "channel_heartbeat_interval_seconds",
+                &self.channel_heartbeat_interval_seconds,
+            )
+            .finish()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            service_type: String::from("rpc"),
+            node: Node::default(),
+            nodes: vec![],
+            load_balance_strategy: default_load_balance_strategy(),
+            node_probe_interval_seconds: default_node_probe_interval_seconds(),
+            contract: Contract::default(),
+            account: String::default(),
+            account_pem: None,
+            account_password: None,
+            remote_signer: None,
+            accounts_directory: None,
+            signer: None,
+            authentication: Authentication::default(),
+            rpc: RPCConfig::default(),
+            retry: None,
+            sm_crypto: false,
+            group_id: default_group_id(),
+            chain_id: default_chain_id(),
+            timeout_seconds: default_timeout_seconds(),
+            channel_pool_size: default_channel_pool_size(),
+            channel_heartbeat_interval_seconds: default_channel_heartbeat_interval_seconds(),
+        }
+    }
 }
 
 impl Config {
-    fn get_file_real_path(&self, base_path: &Path, file_path: &str) -> String {
-        if file_path.len() > 0 {
-            fs::canonicalize(base_path.join(file_path))
-                .unwrap()
-                .display()
-                .to_string()
+    ///
+    /// 配置了 `nodes` 时返回 `nodes`，否则返回仅包含 `node` 的列表。
+    pub fn get_nodes(&self) -> Vec<Node> {
+        if self.nodes.is_empty() {
+            vec![self.node.clone()]
         } else {
-            String::default()
+            self.nodes.clone()
+        }
+    }
+
+    ///
+    /// 返回以 node 为唯一服务节点的配置副本。
+    pub fn with_node(&self, node: &Node) -> Config {
+        let mut config = self.clone();
+        config.node = node.clone();
+        config.nodes = vec![];
+        config
+    }
+
+    ///
+    /// 文件不存在时返回拼接后的路径，由 `validate` 报告该问题。
+    fn get_file_real_path(&self, base_path: &Path, file_path: &str) -> String {
+        if file_path.is_empty() {
+            return String::default();
         }
+        let path = base_path.join(file_path);
+        fs::canonicalize(&path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
     }
 
     pub fn convert_paths(&mut self, base_path: &Path) {
         self.account = self.get_file_real_path(base_path, &self.account);
+        self.accounts_directory = self
+            .accounts_directory
+            .as_ref()
+            .map(|accounts_directory| self.get_file_real_path(base_path, accounts_directory));
         self.contract = Contract {
             solc: self.get_file_real_path(base_path, &self.contract.solc),
             source: self.get_file_real_path(base_path, &self.contract.source),
@@ -82,13 +489,925 @@ impl Config {
             sign_key: self.get_file_real_path(base_path, &self.authentication.sign_key),
             enc_key: self.get_file_real_path(base_path, &self.authentication.enc_key),
             enc_cert: self.get_file_real_path(base_path, &self.authentication.enc_cert),
+            ..self.authentication.clone()
+        };
+        self.rpc.ca_cert = self.get_file_real_path(base_path, &self.rpc.ca_cert);
+        self.rpc.client_cert = self.get_file_real_path(base_path, &self.rpc.client_cert);
+        self.rpc.client_key = self.get_file_real_path(base_path, &self.rpc.client_key);
+    }
+
+    ///
+    /// 检查配置是否可用，一次性返回所有问题（`ConfigError::InvalidConfig`）。
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = vec![];
+        match self.service_type.as_str() {
+            "rpc" | "channel" => {}
+            service_type => problems.push(format!(
+                "Unknown service_type {:?}, expected `rpc` or `channel`",
+                service_type
+            )),
+        }
+        match self.load_balance_strategy.as_str() {
+            "round_robin" | "priority" => {}
+            load_balance_strategy => problems.push(format!(
+                "Unknown load_balance_strategy {:?}, expected `round_robin` or `priority`",
+                load_balance_strategy
+            )),
+        }
+        if self.timeout_seconds <= 0 {
+            problems.push(format!(
+                "timeout_seconds should be positive, got {:}",
+                self.timeout_seconds
+            ));
+        }
+        if self.sm_crypto && !is_sm_crypto_enabled() {
+            problems.push(SM_CRYPTO_DISABLED_MESSAGE.to_owned());
+        }
+
+        if let Some(remote_signer) = &self.remote_signer {
+            let https_supported =
+                cfg!(feature = "rpc") && remote_signer.url.starts_with("https://");
+            if !remote_signer.url.starts_with("http://") && !https_supported {
+                problems.push(format!(
+                    "remote_signer.url should start with http:// or https:// (requires the `rpc` \
+                     feature), got {:?}",
+                    remote_signer.url
+                ));
+            }
+            match hex::decode(remote_signer.public_key.trim_start_matches("0x")) {
+                Ok(public_key) if public_key.len() == 64 || public_key.len() == 65 => {}
+                _ => problems.push(format!(
+                    "remote_signer.public_key {:?} is not a valid public key",
+                    remote_signer.public_key
+                )),
+            }
+        }
+
+        if let Some(accounts_directory) = &self.accounts_directory {
+            if !Path::new(accounts_directory).is_dir() {
+                problems.push(format!(
+                    "accounts_directory {:?} is not a directory",
+                    accounts_directory
+                ));
+            }
+        }
+
+        let has_signer = self.signer.is_some() || self.remote_signer.is_some();
+        if !has_signer && self.account.is_empty() && self.account_pem.is_none() {
+            problems.push("account is required".to_owned());
+        } else if !has_signer {
+            let account_contents = read_pem(&self.account_pem, &self.account).ok();
+            let account_file_format = account_contents
+                .as_ref()
+                .map(|contents| get_account_file_format(contents));
+            match account_file_format {
+                Some(AccountFileFormat::Pem) | None => {
+                    // 与加载账户时一致，以 DER SEQUENCE 开头的内容为 DER 编码的私钥，无需 pem 解码。
+                    let contents = match account_contents {
+                        Some(contents) if contents.first() == Some(&DER_SEQUENCE) => Some(contents),
+                        _ => check_pem(
+                            &mut problems,
+                            "account",
+                            &self.account,
+                            pem_bytes(&self.account_pem),
+                        ),
+                    };
+                    if let Some(contents) = contents {
+                        match is_sm_private_key(&contents) {
+                            Some(sm_key) if sm_key != self.sm_crypto => problems.push(format!(
+                                "account is a {:} key, which does not match sm_crypto = {:}",
+                                if sm_key { "sm2" } else { "secp256k1" },
+                                self.sm_crypto
+                            )),
+                            _ => {}
+                        }
+                    }
+                }
+                Some(_) if self.account_password.is_none() => {
+                    problems.push("account is encrypted, account_password is required".to_owned())
+                }
+                Some(_) => {}
+            }
+        }
+
+        if self.service_type == "channel" {
+            let authentication = &self.authentication;
+            let mut required_fields = vec![
+                (
+                    "authentication.ca_cert",
+                    &authentication.ca_cert,
+                    authentication.ca_cert_pem.is_some(),
+                ),
+                (
+                    "authentication.sign_cert",
+                    &authentication.sign_cert,
+                    authentication.sign_cert_pem.is_some(),
+                ),
+                (
+                    "authentication.sign_key",
+                    &authentication.sign_key,
+                    authentication.sign_key_pem.is_some(),
+                ),
+            ];
+            if self.sm_crypto {
+                required_fields.push((
+                    "authentication.enc_key",
+                    &authentication.enc_key,
+                    authentication.enc_key_pem.is_some(),
+                ));
+                required_fields.push((
+                    "authentication.enc_cert",
+                    &authentication.enc_cert,
+                    authentication.enc_cert_pem.is_some(),
+                ));
+            }
+            for (name, file_path, has_pem) in required_fields {
+                if file_path.is_empty() && !has_pem {
+                    problems.push(format!("{:} is required in channel mode", name));
+                }
+            }
+        }
+
+        let pem_files = [
+            (
+                "authentication.ca_cert",
+                &self.authentication.ca_cert,
+                pem_bytes(&self.authentication.ca_cert_pem),
+            ),
+            (
+                "authentication.sign_cert",
+                &self.authentication.sign_cert,
+                pem_bytes(&self.authentication.sign_cert_pem),
+            ),
+            (
+                "authentication.sign_key",
+                &self.authentication.sign_key,
+                pem_bytes(&self.authentication.sign_key_pem),
+            ),
+            (
+                "authentication.enc_key",
+                &self.authentication.enc_key,
+                pem_bytes(&self.authentication.enc_key_pem),
+            ),
+            (
+                "authentication.enc_cert",
+                &self.authentication.enc_cert,
+                pem_bytes(&self.authentication.enc_cert_pem),
+            ),
+            (
+                "rpc.ca_cert",
+                &self.rpc.ca_cert,
+                pem_bytes(&self.rpc.ca_cert_pem),
+            ),
+            (
+                "rpc.client_cert",
+                &self.rpc.client_cert,
+                pem_bytes(&self.rpc.client_cert_pem),
+            ),
+            (
+                "rpc.client_key",
+                &self.rpc.client_key,
+                pem_bytes(&self.rpc.client_key_pem),
+            ),
+        ];
+        for (name, file_path, pem) in pem_files.iter() {
+            check_pem(&mut problems, name, file_path, *pem);
+        }
+
+        let contract_paths = [
+            ("contract.solc", &self.contract.solc),
+            ("contract.source", &self.contract.source),
+            ("contract.output", &self.contract.output),
+        ];
+        for (name, path) in contract_paths.iter() {
+            if !path.is_empty() && !Path::new(path).exists() {
+                problems.push(format!("{:} {:} does not exist", name, path));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::InvalidConfig { problems })
+        }
+    }
+}
+
+///
+/// 无需配置文件，以代码方式构建配置，证书及私钥可直接使用内存中的 pem 内容。
+///
+/// 文件路径按原样使用（相对路径相对于当前工作目录），`build` 时会调用 `Config::validate` 检查配置。
+#[derive(Clone, Debug)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn new(service_type: &str) -> ConfigBuilder {
+        ConfigBuilder {
+            config: Config {
+                service_type: service_type.to_owned(),
+                ..Config::default()
+            },
+        }
+    }
+
+    pub fn node(mut self, host: &str, port: i32) -> ConfigBuilder {
+        self.config.node = Node {
+            host: host.to_owned(),
+            port,
+            priority: 0,
         };
+        self
+    }
+
+    ///
+    /// 添加到 `nodes` 中，设置后将忽略 `node`。
+    pub fn add_node(mut self, host: &str, port: i32, priority: i32) -> ConfigBuilder {
+        self.config.nodes.push(Node {
+            host: host.to_owned(),
+            port,
+            priority,
+        });
+        self
+    }
+
+    pub fn load_balance_strategy(mut self, load_balance_strategy: &str) -> ConfigBuilder {
+        self.config.load_balance_strategy = load_balance_strategy.to_owned();
+        self
+    }
+
+    pub fn group_id(mut self, group_id: u32) -> ConfigBuilder {
+        self.config.group_id = group_id;
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u32) -> ConfigBuilder {
+        self.config.chain_id = chain_id;
+        self
+    }
+
+    pub fn timeout_seconds(mut self, timeout_seconds: i64) -> ConfigBuilder {
+        self.config.timeout_seconds = timeout_seconds;
+        self
+    }
+
+    pub fn sm_crypto(mut self, sm_crypto: bool) -> ConfigBuilder {
+        self.config.sm_crypto = sm_crypto;
+        self
+    }
+
+    pub fn account_file(mut self, account: &str) -> ConfigBuilder {
+        self.config.account = account.to_owned();
+        self
+    }
+
+    pub fn account_pem(mut self, account_pem: &[u8]) -> ConfigBuilder {
+        self.config.account_pem = Some(Zeroizing::new(account_pem.to_vec()));
+        self
+    }
+
+    pub fn account_password(mut self, account_password: &str) -> ConfigBuilder {
+        self.config.account_password = Some(account_password.to_owned());
+        self
+    }
+
+    pub fn remote_signer(mut self, url: &str, public_key: &str) -> ConfigBuilder {
+        self.config.remote_signer = Some(RemoteSignerConfig {
+            url: url.to_owned(),
+            public_key: public_key.to_owned(),
+            bearer_token: None,
+        });
+        self
+    }
+
+    pub fn accounts_directory(mut self, accounts_directory: &str) -> ConfigBuilder {
+        self.config.accounts_directory = Some(accounts_directory.to_owned());
+        self
+    }
+
+    pub fn signer(mut self, signer: Arc<dyn Signer + Send + Sync>) -> ConfigBuilder {
+        self.config.signer = Some(signer);
+        self
+    }
+
+    pub fn contract(mut self, contract: Contract) -> ConfigBuilder {
+        self.config.contract = contract;
+        self
+    }
+
+    pub fn authentication(mut self, authentication: Authentication) -> ConfigBuilder {
+        self.config.authentication = authentication;
+        self
+    }
+
+    ///
+    /// 设置 `channel` 模式下的 CA 证书、签名证书及签名私钥的 pem 内容。
+    pub fn authentication_pem(
+        mut self,
+        ca_cert: &[u8],
+        sign_cert: &[u8],
+        sign_key: &[u8],
+    ) -> ConfigBuilder {
+        self.config.authentication.ca_cert_pem = Some(ca_cert.to_vec());
+        self.config.authentication.sign_cert_pem = Some(sign_cert.to_vec());
+        self.config.authentication.sign_key_pem = Some(Zeroizing::new(sign_key.to_vec()));
+        self
+    }
+
+    ///
+    /// 设置`国密`模式下 enc 证书及 enc 私钥的 pem 内容。
+    pub fn authentication_enc_pem(mut self, enc_key: &[u8], enc_cert: &[u8]) -> ConfigBuilder {
+        self.config.authentication.enc_key_pem = Some(Zeroizing::new(enc_key.to_vec()));
+        self.config.authentication.enc_cert_pem = Some(enc_cert.to_vec());
+        self
+    }
+
+    pub fn rpc(mut self, rpc: RPCConfig) -> ConfigBuilder {
+        self.config.rpc = rpc;
+        self
+    }
+
+    pub fn rpc_ca_cert_pem(mut self, ca_cert: &[u8]) -> ConfigBuilder {
+        self.config.rpc.ca_cert_pem = Some(ca_cert.to_vec());
+        self
+    }
+
+    pub fn rpc_client_pem(mut self, client_cert: &[u8], client_key: &[u8]) -> ConfigBuilder {
+        self.config.rpc.client_cert_pem = Some(client_cert.to_vec());
+        self.config.rpc.client_key_pem = Some(Zeroizing::new(client_key.to_vec()));
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> ConfigBuilder {
+        self.config.retry = Some(retry);
+        self
+    }
+
+    pub fn channel_pool_size(mut self, channel_pool_size: usize) -> ConfigBuilder {
+        self.config.channel_pool_size = channel_pool_size;
+        self
+    }
+
+    pub fn channel_heartbeat_interval_seconds(mut self, interval_seconds: u64) -> ConfigBuilder {
+        self.config.channel_heartbeat_interval_seconds = interval_seconds;
+        self
+    }
+
+    pub fn build(self) -> Result<Config, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+///
+/// 返回内存中的 pem 内容，未设置时读取 file_path 对应的文件（内容可能为私钥，释放时清零）。
+pub(crate) fn read_pem<T: AsRef<[u8]>>(
+    pem: &Option<T>,
+    file_path: &str,
+) -> std::io::Result<Zeroizing<Vec<u8>>> {
+    match pem {
+        Some(pem) => Ok(Zeroizing::new(pem.as_ref().to_vec())),
+        None => fs::read(file_path).map(Zeroizing::new),
+    }
+}
+
+///
+/// 内存中的 pem 内容，供 `check_pem` 统一处理证书及私钥。
+fn pem_bytes<T: AsRef<[u8]>>(pem: &Option<T>) -> Option<&[u8]> {
+    pem.as_ref().map(|pem| pem.as_ref())
+}
+
+///
+/// 读取并解析 pem 内容，失败时记录问题并返回 None，未设置时直接返回 None。
+fn check_pem(
+    problems: &mut Vec<String>,
+    name: &str,
+    file_path: &str,
+    pem: Option<&[u8]>,
+) -> Option<Zeroizing<Vec<u8>>> {
+    if file_path.is_empty() && pem.is_none() {
+        return None;
+    }
+    let source = if pem.is_some() {
+        "(in memory)"
+    } else {
+        file_path
+    };
+    let content = match read_pem(&pem, file_path) {
+        Ok(content) => content,
+        Err(err) => {
+            problems.push(format!("{:} {:} is not readable: {:}", name, source, err));
+            return None;
+        }
+    };
+    match pem::parse(&content[..]) {
+        Ok(pem) => Some(Zeroizing::new(pem.contents)),
+        Err(err) => {
+            problems.push(format!(
+                "{:} {:} is not a valid pem file: {:}",
+                name, source, err
+            ));
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum EnvValueKind {
+    String,
+    Number,
+    Bool,
+    Json,
+}
+
+///
+/// 可通过环境变量覆盖的配置项，环境变量名为 `ENV_PREFIX` 加上以 `_` 连接的大写路径。
+const ENV_OVERRIDE_FIELDS: &[(&[&str], EnvValueKind)] = &[
+    (&["service_type"], EnvValueKind::String),
+    (&["node", "host"], EnvValueKind::String),
+    (&["node", "port"], EnvValueKind::Number),
+    (&["node", "priority"], EnvValueKind::Number),
+    (&["nodes"], EnvValueKind::Json),
+    (&["load_balance_strategy"], EnvValueKind::String),
+    (&["node_probe_interval_seconds"], EnvValueKind::Number),
+    (&["contract", "solc"], EnvValueKind::String),
+    (&["contract", "source"], EnvValueKind::String),
+    (&["contract", "output"], EnvValueKind::String),
+    (&["account"], EnvValueKind::String),
+    (&["account_password"], EnvValueKind::String),
+    (&["remote_signer", "url"], EnvValueKind::String),
+    (&["remote_signer", "public_key"], EnvValueKind::String),
+    (&["remote_signer", "bearer_token"], EnvValueKind::String),
+    (&["accounts_directory"], EnvValueKind::String),
+    (&["authentication", "ca_cert"], EnvValueKind::String),
+    (&["authentication", "sign_cert"], EnvValueKind::String),
+    (&["authentication", "sign_key"], EnvValueKind::String),
+    (&["authentication", "enc_key"], EnvValueKind::String),
+    (&["authentication", "enc_cert"], EnvValueKind::String),
+    (&["rpc", "scheme"], EnvValueKind::String),
+    (&["rpc", "ca_cert"], EnvValueKind::String),
+    (&["rpc", "client_cert"], EnvValueKind::String),
+    (&["rpc", "client_key"], EnvValueKind::String),
+    (&["rpc", "username"], EnvValueKind::String),
+    (&["rpc", "password"], EnvValueKind::String),
+    (&["rpc", "bearer_token"], EnvValueKind::String),
+    (&["rpc", "proxy"], EnvValueKind::String),
+    (&["retry"], EnvValueKind::Json),
+    (&["sm_crypto"], EnvValueKind::Bool),
+    (&["group_id"], EnvValueKind::Number),
+    (&["chain_id"], EnvValueKind::Number),
+    (&["timeout_seconds"], EnvValueKind::Number),
+    (&["channel_pool_size"], EnvValueKind::Number),
+    (
+        &["channel_heartbeat_interval_seconds"],
+        EnvValueKind::Number,
+    ),
+];
+
+fn parse_env_value(name: &str, value: &str, kind: EnvValueKind) -> Result<JSONValue, ConfigError> {
+    let parsed_value = match kind {
+        EnvValueKind::String => return Ok(JSONValue::String(value.to_owned())),
+        EnvValueKind::Bool => match value.trim().to_lowercase().as_str() {
+            "true" | "1" => Some(JSONValue::Bool(true)),
+            "false" | "0" => Some(JSONValue::Bool(false)),
+            _ => None,
+        },
+        EnvValueKind::Number => serde_json::from_str::<JSONValue>(value.trim())
+            .ok()
+            .filter(|value| value.is_number()),
+        EnvValueKind::Json => serde_json::from_str::<JSONValue>(value).ok(),
+    };
+    parsed_value.ok_or_else(|| ConfigError::CustomError {
+        message: format!(
+            "Invalid value {:?} of environment variable {:}",
+            value, name
+        ),
+    })
+}
+
+fn set_json_value(config_value: &mut JSONValue, path: &[&str], value: JSONValue) {
+    if !config_value.is_object() {
+        *config_value = JSONValue::Object(JSONMap::new());
+    }
+    if let JSONValue::Object(map) = config_value {
+        if path.len() == 1 {
+            map.insert(path[0].to_owned(), value);
+        } else {
+            let child = map
+                .entry(path[0].to_owned())
+                .or_insert_with(|| JSONValue::Object(JSONMap::new()));
+            set_json_value(child, &path[1..], value);
+        }
+    }
+}
+
+///
+/// 使用 vars 中以 `ENV_PREFIX` 开头的变量覆盖 config_value 中对应的配置项。
+fn apply_env_overrides<I>(config_value: &mut JSONValue, vars: I) -> Result<(), ConfigError>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    for (name, value) in vars {
+        if !name.starts_with(ENV_PREFIX) {
+            continue;
+        }
+        for (path, kind) in ENV_OVERRIDE_FIELDS {
+            if name == format!("{:}{:}", ENV_PREFIX, path.join("_").to_uppercase()) {
+                set_json_value(config_value, path, parse_env_value(&name, &value, *kind)?);
+                break;
+            }
+        }
     }
+    Ok(())
 }
 
-pub fn create_config_with_file(config_file_path: &str) -> Result<Config, std::io::Error> {
+fn parse_config_content(config_path: &Path, content: &[u8]) -> Result<JSONValue, ConfigError> {
+    let extension = config_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match extension.as_str() {
+        "toml" => {
+            let content = std::str::from_utf8(content).map_err(|err| ConfigError::CustomError {
+                message: format!("Invalid toml config: {:}", err),
+            })?;
+            Ok(toml::from_str(content)?)
+        }
+        "yaml" | "yml" => Ok(serde_yaml::from_slice(content)?),
+        _ => Ok(serde_json::from_slice(content)?),
+    }
+}
+
+///
+/// 未指定 profile 时，使用该环境变量指定的 profile。
+pub const PROFILE_ENV: &str = "FISCO_BCOS_PROFILE";
+
+const PROFILES_KEY: &str = "profiles";
+const DEFAULT_PROFILE_KEY: &str = "default_profile";
+
+///
+/// 将 overlay 合并到 base 中：对象按键递归合并，其他类型的值直接替换。
+fn merge_json_value(base: &mut JSONValue, overlay: JSONValue) {
+    match (base, overlay) {
+        (JSONValue::Object(base_map), JSONValue::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => merge_json_value(base_value, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+///
+/// 从配置中移除 `profiles` 及 `default_profile`，并将选中的 profile 合并到公共配置上。
+///
+/// profile 的选择顺序为：参数 profile、环境变量 `FISCO_BCOS_PROFILE`、配置项 `default_profile`，均未设置时仅使用公共配置。
+fn select_profile(
+    mut config_value: JSONValue,
+    profile: Option<&str>,
+    env_profile: Option<String>,
+) -> Result<JSONValue, ConfigError> {
+    let (profiles, default_profile) = match config_value.as_object_mut() {
+        Some(map) => (map.remove(PROFILES_KEY), map.remove(DEFAULT_PROFILE_KEY)),
+        None => (None, None),
+    };
+    let profile = match profile {
+        Some(profile) => Some(profile.to_owned()),
+        None => env_profile.or_else(|| {
+            default_profile.and_then(|default_profile| default_profile.as_str().map(String::from))
+        }),
+    };
+    let profile = match profile {
+        Some(profile) => profile,
+        None => return Ok(config_value),
+    };
+    match profiles.and_then(|mut profiles| profiles.get_mut(&profile).map(JSONValue::take)) {
+        Some(profile_value) => {
+            merge_json_value(&mut config_value, profile_value);
+            Ok(config_value)
+        }
+        None => Err(ConfigError::CustomError {
+            message: format!("The profile {:?} is not found", profile),
+        }),
+    }
+}
+
+fn read_config_file(config_path: &Path) -> Result<JSONValue, ConfigError> {
+    parse_config_content(config_path, &fs::read(config_path)?)
+}
+
+fn create_config(config_file_path: &str, profile: Option<&str>) -> Result<Config, ConfigError> {
     let config_path = Path::new(config_file_path);
-    let mut config: Config = serde_json::from_slice(fs::read(config_path)?.as_slice())?;
-    config.convert_paths(config_path.parent().unwrap());
+    let mut config_value = select_profile(
+        read_config_file(config_path)?,
+        profile,
+        env::var(PROFILE_ENV).ok(),
+    )?;
+    apply_env_overrides(&mut config_value, env::vars())?;
+    let mut config: Config = serde_json::from_value(config_value)?;
+    config.convert_paths(config_path.parent().unwrap_or_else(|| Path::new("")));
+    config.validate()?;
     Ok(config)
 }
+
+///
+/// 根据配置文件创建配置，文件格式由扩展名决定：`.toml` 为 TOML，`.yaml`、`.yml` 为 YAML，其余均按 JSON 解析。
+///
+/// 配置文件中包含 `profiles` 时，使用环境变量 `FISCO_BCOS_PROFILE` 或配置项 `default_profile` 指定的 profile。
+///
+/// 以 `FISCO_BCOS_` 开头的环境变量会覆盖文件中对应的配置项，文件及环境变量中的相对路径均相对于配置文件所在目录。
+///
+/// 返回前会调用 `Config::validate` 检查配置。
+pub fn create_config_with_file(config_file_path: &str) -> Result<Config, ConfigError> {
+    create_config(config_file_path, None)
+}
+
+///
+/// 与 `create_config_with_file` 相同，但使用指定的 profile。
+pub fn create_config_with_profile(
+    config_file_path: &str,
+    profile: &str,
+) -> Result<Config, ConfigError> {
+    create_config(config_file_path, Some(profile))
+}
+
+///
+/// 返回配置文件中所有 profile 的名称。
+pub fn get_config_profiles(config_file_path: &str) -> Result<Vec<String>, ConfigError> {
+    let config_value = read_config_file(Path::new(config_file_path))?;
+    Ok(match config_value[PROFILES_KEY].as_object() {
+        Some(profiles) => profiles.keys().cloned().collect(),
+        None => vec![],
+    })
+}
+
+///
+/// 仅根据以 `FISCO_BCOS_` 开头的环境变量创建配置，相对路径相对于当前工作目录。
+///
+/// 返回前会调用 `Config::validate` 检查配置。
+pub fn create_config_with_env() -> Result<Config, ConfigError> {
+    let mut config_value = JSONValue::Object(JSONMap::new());
+    apply_env_overrides(&mut config_value, env::vars())?;
+    let mut config: Config = serde_json::from_value(config_value)?;
+    config.convert_paths(&env::current_dir()?);
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, path::Path};
+
+    use serde_json::Value as JSONValue;
+
+    use crate::config::*;
+
+    fn to_vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_config_content() {
+        let toml_content = r#"
+            service_type = "rpc"
+            account = "./accounts/alice.pem"
+
+            [node]
+            host = "127.0.0.1"
+            port = 8545
+        "#;
+        let yaml_content = r#"
+            service_type: rpc
+            account: ./accounts/alice.pem
+            node:
+              host: 127.0.0.1
+              port: 8545
+        "#;
+        let json_content = r#"{
+            "service_type": "rpc",
+            "account": "./accounts/alice.pem",
+            "node": { "host": "127.0.0.1", "port": 8545 }
+        }"#;
+        let expected_value =
+            parse_config_content(Path::new("config.json"), json_content.as_bytes()).unwrap();
+        for (file_name, content) in [
+            ("config.toml", toml_content),
+            ("config.yaml", yaml_content),
+            ("config.yml", yaml_content),
+        ] {
+            let value = parse_config_content(Path::new(file_name), content.as_bytes()).unwrap();
+            assert_eq!(expected_value, value);
+        }
+
+        let config: Config = serde_json::from_value(expected_value).unwrap();
+        assert_eq!(1, config.group_id);
+        assert_eq!(1, config.chain_id);
+        assert_eq!(10, config.timeout_seconds);
+        assert!(!config.sm_crypto);
+    }
+
+    #[test]
+    fn test_apply_env_overrides() {
+        let mut config_value: JSONValue =
+            serde_json::from_str(r#"{"service_type": "rpc", "node": {"host": "127.0.0.1"}}"#)
+                .unwrap();
+        let vars = to_vars(&[
+            ("FISCO_BCOS_NODE_HOST", "192.168.0.2"),
+            ("FISCO_BCOS_NODE_PORT", "20200"),
+            ("FISCO_BCOS_GROUP_ID", "2"),
+            ("FISCO_BCOS_SM_CRYPTO", "true"),
+            ("FISCO_BCOS_SERVICE_TYPE", "channel"),
+            ("FISCO_BCOS_UNKNOWN", "ignored"),
+            ("HOME", "/root"),
+        ]);
+        apply_env_overrides(&mut config_value, vars).unwrap();
+        let config: Config = serde_json::from_value(config_value).unwrap();
+        assert_eq!("channel", config.service_type);
+        assert_eq!("192.168.0.2", config.node.host);
+        assert_eq!(20200, config.node.port);
+        assert_eq!(2, config.group_id);
+        assert!(config.sm_crypto);
+
+        let mut config_value = JSONValue::Null;
+        let vars = to_vars(&[("FISCO_BCOS_GROUP_ID", "two")]);
+        assert!(apply_env_overrides(&mut config_value, vars).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "service_type": "channel",
+            "account": "./not_exists/alice.pem",
+            "timeout_seconds": 0,
+            "load_balance_strategy": "random",
+        }))
+        .unwrap();
+        match config.validate() {
+            Err(ConfigError::InvalidConfig { problems }) => assert_eq!(6, problems.len()),
+            _ => panic!("expected ConfigError::InvalidConfig"),
+        }
+
+        let mut account_file = tempfile::Builder::new().suffix(".pem").tempfile().unwrap();
+        let sm_private_key = hex::decode(
+            "308187020100301306072a8648ce3d020106082a811ccf5501822d046d306b0201010420\
+             0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        account_file
+            .write_all(
+                pem::encode(&pem::Pem {
+                    tag: "PRIVATE KEY".to_owned(),
+                    contents: sm_private_key,
+                })
+                .as_bytes(),
+            )
+            .unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "service_type": "rpc",
+            "account": account_file.path().display().to_string(),
+        }))
+        .unwrap();
+        match config.validate() {
+            Err(ConfigError::InvalidConfig { problems }) => {
+                assert_eq!(1, problems.len());
+                assert!(problems[0].contains("sm2"));
+            }
+            _ => panic!("expected ConfigError::InvalidConfig"),
+        }
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let private_key = hex::decode(
+            "308184020100301006072a8648ce3d020106052b8104000a046d306b0201010420\
+             0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let account_pem = pem::encode(&pem::Pem {
+            tag: "PRIVATE KEY".to_owned(),
+            contents: private_key.clone(),
+        });
+        let config = ConfigBuilder::new("rpc")
+            .node("127.0.0.1", 8545)
+            .group_id(2)
+            .timeout_seconds(3)
+            .account_pem(account_pem.as_bytes())
+            .build()
+            .unwrap();
+        assert_eq!("127.0.0.1", config.node.host);
+        assert_eq!(2, config.group_id);
+        assert_eq!(3, config.timeout_seconds);
+        assert!(format!("{:?}", config).contains("account_pem: Some(\"[REDACTED]\")"));
+        assert_eq!(
+            Some(account_pem.into_bytes()),
+            config.account_pem.map(|pem| pem.to_vec())
+        );
+
+        // 加载账户时接受 DER 编码的私钥，校验时同样接受。
+        let config = ConfigBuilder::new("rpc")
+            .account_pem(&private_key)
+            .build()
+            .unwrap();
+        assert_eq!(
+            Some(private_key),
+            config.account_pem.map(|pem| pem.to_vec())
+        );
+
+        let result = ConfigBuilder::new("channel")
+            .account_pem(b"invalid pem")
+            .authentication_pem(b"invalid pem", b"invalid pem", b"invalid pem")
+            .build();
+        match result {
+            Err(ConfigError::InvalidConfig { problems }) => assert_eq!(4, problems.len()),
+            _ => panic!("expected ConfigError::InvalidConfig"),
+        }
+
+        let public_key = "0x".to_owned() + &"11".repeat(64);
+        let result = ConfigBuilder::new("rpc")
+            .remote_signer("https://127.0.0.1:8443/sign", &public_key)
+            .build();
+        assert_eq!(cfg!(feature = "rpc"), result.is_ok());
+        let result = ConfigBuilder::new("rpc")
+            .remote_signer("ftp://127.0.0.1/sign", &public_key)
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_debug_redaction() {
+        let sign_key_pem = b"sign key secret".to_vec();
+        let enc_key_pem = b"enc key secret".to_vec();
+        let client_key_pem = b"client key secret".to_vec();
+        let config = Config {
+            account_password: Some("account password secret".to_owned()),
+            authentication: Authentication {
+                sign_key_pem: Some(Zeroizing::new(sign_key_pem.clone())),
+                enc_key_pem: Some(Zeroizing::new(enc_key_pem.clone())),
+                ..Authentication::default()
+            },
+            rpc: RPCConfig {
+                username: "fisco".to_owned(),
+                password: "rpc password secret".to_owned(),
+                bearer_token: "bearer token secret".to_owned(),
+                client_key_pem: Some(Zeroizing::new(client_key_pem.clone())),
+                ..RPCConfig::default()
+            },
+            ..Config::default()
+        };
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("secret"));
+        for pem in [&sign_key_pem, &enc_key_pem, &client_key_pem] {
+            assert!(!debug.contains(&format!("{:?}", pem)));
+        }
+        assert!(debug.contains("username: \"fisco\""));
+        assert!(debug.contains("password: \"[REDACTED]\""));
+        assert!(debug.contains("sign_key_pem: Some(\"[REDACTED]\")"));
+        assert!(debug.contains("client_key_pem: Some(\"[REDACTED]\")"));
+    }
+
+    #[test]
+    fn test_select_profile() {
+        let config_value: JSONValue = serde_json::from_str(
+            r#"{
+                "service_type": "rpc",
+                "timeout_seconds": 5,
+                "contract": { "source": "./contracts", "output": "./contracts/.output" },
+                "default_profile": "dev",
+                "profiles": {
+                    "dev": { "node": { "host": "127.0.0.1", "port": 8545 } },
+                    "prod": {
+                        "node": { "host": "10.0.0.1", "port": 8545 },
+                        "contract": { "output": "./prod/.output" }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let value = select_profile(config_value.clone(), None, None).unwrap();
+        assert_eq!("127.0.0.1", value["node"]["host"]);
+        assert!(value.get("profiles").is_none());
+        assert!(value.get("default_profile").is_none());
+
+        let value = select_profile(config_value.clone(), None, Some("prod".to_owned())).unwrap();
+        assert_eq!("10.0.0.1", value["node"]["host"]);
+        assert_eq!("./contracts", value["contract"]["source"]);
+        assert_eq!("./prod/.output", value["contract"]["output"]);
+        assert_eq!(5, value["timeout_seconds"]);
+
+        let value =
+            select_profile(config_value.clone(), Some("dev"), Some("prod".to_owned())).unwrap();
+        assert_eq!("127.0.0.1", value["node"]["host"]);
+
+        assert!(select_profile(config_value, Some("staging"), None).is_err());
+    }
+}
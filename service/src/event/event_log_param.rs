@@ -8,6 +8,7 @@ pub struct EventLogParam {
     to_block: Arc<RwLock<String>>,
     addresses: Arc<RwLock<Vec<String>>>,
     topics: Arc<RwLock<Vec<String>>>,
+    group_id: Arc<RwLock<Option<u32>>>,
 }
 
 impl EventLogParam {
@@ -18,6 +19,7 @@ impl EventLogParam {
             to_block: Arc::new(RwLock::new(String::from("latest"))),
             addresses: Arc::new(RwLock::new(vec![])),
             topics: Arc::new(RwLock::new(vec![])),
+            group_id: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -102,4 +104,18 @@ impl EventLogParam {
             topics_write_lock.remove(removed_index);
         }
     }
+
+    ///
+    /// 未设置时使用配置中的 `group_id`。
+    pub fn get_group_id(&self) -> Option<u32> {
+        let group_id_lock = self.group_id.clone();
+        let group_id_read_lock = group_id_lock.read().unwrap();
+        *group_id_read_lock
+    }
+
+    pub fn set_group_id(&self, group_id: u32) {
+        let group_id_lock = self.group_id.clone();
+        let mut group_id_write_lock = group_id_lock.write().unwrap();
+        *group_id_write_lock = Some(group_id);
+    }
 }
@@ -1,23 +1,22 @@
 use std::{
-    collections::HashSet,
+    collections::HashMap,
     sync::{Arc, RwLock},
-    thread,
     time::Duration,
 };
 
 use serde_json::{json, Value as JSONValue};
 use thiserror::Error;
+use tokio::sync::{mpsc::UnboundedReceiver, watch};
 
 use crate::{
-    channel::{
-        channel_read, open_tassl, pack_amop_message, pack_channel_message, ChannelError,
-        MessageType,
-    },
+    channel::{pack_amop_message, ChannelError, ChannelMessage, MessageType},
+    channel_pool::ChannelConnection,
     config::Config,
     event::{event_emitter::EventEmitter, event_log_param::EventLogParam},
 };
 
 type EventEmitterResult = Result<JSONValue, EventServiceError>;
+type EventConnection = (ChannelConnection, UnboundedReceiver<ChannelMessage>);
 
 #[derive(Error, Debug)]
 pub enum EventServiceError {
@@ -31,7 +30,7 @@ pub enum EventServiceError {
 pub struct EventService<'l> {
     config: Config,
     event_emitter: EventEmitter<'l, EventEmitterResult>,
-    event_loop_lock: Arc<RwLock<HashSet<String>>>,
+    event_loop_lock: Arc<RwLock<HashMap<String, watch::Sender<bool>>>>,
 }
 
 impl<'l> EventService<'l> {
@@ -46,101 +45,135 @@ impl<'l> EventService<'l> {
     fn get_event_loop_running_status(&self, key: &str) -> bool {
         let rw_lock = self.event_loop_lock.clone();
         let read_lock = rw_lock.read().unwrap();
-        read_lock.contains(key)
+        read_lock.contains_key(key)
     }
 
-    fn set_event_loop_running_status(&self, key: &str, status: bool) {
+    ///
+    /// 标记 loop 为运行状态，返回的 receiver 会在 `stop_event_loop` 被调用时收到通知。
+    fn start_event_loop(&self, key: &str) -> watch::Receiver<bool> {
         let rw_lock = self.event_loop_lock.clone();
         let mut write_lock = rw_lock.write().unwrap();
-        if write_lock.contains(key) {
-            write_lock.remove(key);
-        }
-        if status {
-            write_lock.insert(key.to_owned());
-        }
+        write_lock
+            .entry(key.to_owned())
+            .or_insert_with(|| watch::channel(false).0)
+            .subscribe()
     }
 
     fn stop_event_loop(&self, key: &str) {
-        self.set_event_loop_running_status(key, false);
+        let rw_lock = self.event_loop_lock.clone();
+        let mut write_lock = rw_lock.write().unwrap();
+        if let Some(stop_sender) = write_lock.remove(key) {
+            let _ = stop_sender.send(true);
+        }
+    }
+
+    async fn open_event_connection(
+        &self,
+        request_data: &[u8],
+        message_type: MessageType,
+    ) -> Result<EventConnection, ChannelError> {
+        let (connection, receiver) =
+            ChannelConnection::open_with_push_receiver(&self.config).await?;
+        connection.send(request_data, message_type)?;
+        Ok((connection, receiver))
     }
 
-    fn run_event_loop(
+    ///
+    /// 连接失效（心跳超时或连接断开）时会收到 `ChannelError::DeadConnection`，
+    /// 重试时会重新建立连接并注册监听。
+    ///
+    /// 等待推送、重连及重试前的等待均会被 `stop_event_loop` 立即打断。
+    async fn run_event_loop(
         &self,
         key: &str,
         request_data: &[u8],
+        message_type: MessageType,
         sleep_seconds: u32,
         max_retry_times: i32,
     ) {
-        match open_tassl(&self.config) {
-            Ok(tassl) => {
-                match tassl.write(&request_data) {
-                    Ok(_) => {
-                        let mut remain_retry_times = max_retry_times;
-                        self.set_event_loop_running_status(key, true);
-                        while self.get_event_loop_running_status(key) {
-                            match channel_read(&tassl) {
-                                Ok(value) => {
-                                    remain_retry_times = max_retry_times;
-                                    self.event_emitter.emit(key, &Ok(value));
-                                }
-                                Err(err) => {
-                                    if max_retry_times != -1 && remain_retry_times == 0 {
-                                        let err = EventServiceError::CustomError {
-                                            message: format!(
-                                                "SSL_read invoked had failed over {:?} times, \
-                                                 stopping the loop now",
-                                                max_retry_times
-                                            ),
-                                        };
-                                        self.event_emitter.emit(key, &Err(err));
-                                        self.stop_event_loop(key);
-                                        break;
-                                    } else if !self.get_event_loop_running_status(key) {
-                                        let err = EventServiceError::CustomError {
-                                            message: "SSL_read invoked failed, and the event loop \
-                                                      had been stopped"
-                                                .to_string(),
-                                        };
-                                        self.event_emitter.emit(key, &Err(err));
-                                        break;
-                                    } else {
-                                        self.event_emitter
-                                            .emit(key, &Err(EventServiceError::ChannelError(err)));
-                                        remain_retry_times -= 1;
-                                        thread::sleep(Duration::from_millis(
-                                            (sleep_seconds * 1000) as u64,
-                                        ));
-                                    }
-                                }
-                            };
+        let mut event_connection =
+            match self.open_event_connection(request_data, message_type).await {
+                Ok(event_connection) => Some(event_connection),
+                Err(err) => {
+                    self.event_emitter
+                        .emit(key, &Err(EventServiceError::ChannelError(err)));
+                    return;
+                }
+            };
+        let mut remain_retry_times = max_retry_times;
+        let mut stop_receiver = self.start_event_loop(key);
+        while self.get_event_loop_running_status(key) {
+            let result = match event_connection.as_mut() {
+                Some((_, receiver)) => tokio::select! {
+                    message = receiver.recv() => match message {
+                        Some(message) => message.parse_data(),
+                        None => Err(ChannelError::DeadConnection {
+                            message: "The channel connection was closed".to_owned(),
+                        }),
+                    },
+                    _ = stop_receiver.changed() => break,
+                },
+                None => tokio::select! {
+                    result = self.open_event_connection(request_data, message_type) => {
+                        match result {
+                            Ok(new_event_connection) => {
+                                event_connection = Some(new_event_connection);
+                                continue;
+                            }
+                            Err(err) => Err(err),
                         }
+                    },
+                    _ = stop_receiver.changed() => break,
+                },
+            };
+            match result {
+                Ok(value) => {
+                    remain_retry_times = max_retry_times;
+                    self.event_emitter.emit(key, &Ok(value));
+                }
+                Err(err) => {
+                    if let ChannelError::DeadConnection { .. } = err {
+                        event_connection = None;
                     }
-                    Err(err) => {
-                        self.event_emitter.emit(
-                            key,
-                            &Err(EventServiceError::ChannelError(ChannelError::TASSLError(
-                                err,
-                            ))),
-                        );
+                    if max_retry_times != -1 && remain_retry_times == 0 {
+                        let err = EventServiceError::CustomError {
+                            message: format!(
+                                "Channel message read had failed over {:?} times, \
+                                 stopping the loop now",
+                                max_retry_times
+                            ),
+                        };
+                        self.event_emitter.emit(key, &Err(err));
+                        self.stop_event_loop(key);
+                        break;
+                    } else if !self.get_event_loop_running_status(key) {
+                        let err = EventServiceError::CustomError {
+                            message: "Channel message read failed, and the event loop \
+                                      had been stopped"
+                                .to_string(),
+                        };
+                        self.event_emitter.emit(key, &Err(err));
+                        break;
+                    } else {
+                        self.event_emitter
+                            .emit(key, &Err(EventServiceError::ChannelError(err)));
+                        remain_retry_times -= 1;
+                        let sleep_duration = Duration::from_millis((sleep_seconds * 1000) as u64);
+                        tokio::select! {
+                            _ = tokio::time::sleep(sleep_duration) => {},
+                            _ = stop_receiver.changed() => break,
+                        }
                     }
-                };
-            }
-            Err(err) => {
-                self.event_emitter.emit(
-                    key,
-                    &Err(EventServiceError::ChannelError(ChannelError::TASSLError(
-                        err,
-                    ))),
-                );
-            }
-        };
+                }
+            };
+        }
     }
 
     pub fn new(config: &Config) -> EventService<'l> {
         EventService {
-            config: config.clone(),
+            config: config.with_node(&config.get_nodes()[0]),
             event_emitter: EventEmitter::new(),
-            event_loop_lock: Arc::new(RwLock::new(HashSet::new())),
+            event_loop_lock: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -163,14 +196,25 @@ impl<'l> EventService<'l> {
     ///
     /// max_retry_times：链上数据读取失败后，最大重试次数，
     /// 如果失败次数大于指定的值，将主动终止 loop。当值为 -1 时，表示无限循环。
-    pub fn run_block_notify_loop(&self, group_id: u32, sleep_seconds: u32, max_retry_times: i32) {
+    ///
+    /// 该接口为 `async`，需在 tokio 运行时中 `.await`，调用对应的 `stop_*_loop` 后会立即返回。
+    pub async fn run_block_notify_loop(
+        &self,
+        group_id: u32,
+        sleep_seconds: u32,
+        max_retry_times: i32,
+    ) {
         let key = self.get_block_notify_key(group_id);
         let params = json!([format!("_block_notify_{:?}", group_id)]);
-        let request_data = pack_channel_message(
-            &serde_json::to_vec(&params).unwrap(),
+        let request_data = serde_json::to_vec(&params).unwrap();
+        self.run_event_loop(
+            &key,
+            &request_data,
             MessageType::AMOPClientTopics,
-        );
-        self.run_event_loop(&key, &request_data, sleep_seconds, max_retry_times);
+            sleep_seconds,
+            max_retry_times,
+        )
+        .await;
     }
 
     pub fn stop_block_notify_loop(&self, group_id: u32) {
@@ -197,7 +241,9 @@ impl<'l> EventService<'l> {
     ///
     /// max_retry_times：链上数据读取失败后，最大重试次数，
     /// 如果失败次数大于指定的值，将主动终止 loop。当值为 -1 时，表示无限循环。
-    pub fn run_event_log_loop(
+    ///
+    /// 该接口为 `async`，需在 tokio 运行时中 `.await`，调用对应的 `stop_*_loop` 后会立即返回。
+    pub async fn run_event_log_loop(
         &self,
         event_log_param: &EventLogParam,
         sleep_seconds: u32,
@@ -210,12 +256,18 @@ impl<'l> EventService<'l> {
             "toBlock": event_log_param.get_to_block(),
             "addresses": event_log_param.get_addresses(),
             "topics": event_log_param.get_topics(),
-            "groupID": self.config.group_id,
+            "groupID": event_log_param.get_group_id().unwrap_or(self.config.group_id),
             "filterID": event_log_param.get_filter_id(),
         });
-        let amop_data = pack_amop_message(&Vec::from(""), &serde_json::to_vec(&params).unwrap());
-        let request_data = pack_channel_message(&amop_data, MessageType::ClientRegisterEventLog);
-        self.run_event_loop(&key, &request_data, sleep_seconds, max_retry_times);
+        let request_data = pack_amop_message(&Vec::from(""), &serde_json::to_vec(&params).unwrap());
+        self.run_event_loop(
+            &key,
+            &request_data,
+            MessageType::ClientRegisterEventLog,
+            sleep_seconds,
+            max_retry_times,
+        )
+        .await;
     }
 
     pub fn stop_event_log_loop(&self, event_log_param: &EventLogParam) {
@@ -223,3 +275,27 @@ impl<'l> EventService<'l> {
         self.stop_event_loop(&key);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::Config;
+    use crate::event::event_service::*;
+
+    #[tokio::test]
+    async fn test_stop_event_loop() {
+        let event_service = EventService::new(&Config::default());
+        let key = event_service.get_block_notify_key(1);
+        let mut stop_receiver = event_service.start_event_loop(&key);
+        assert!(event_service.get_event_loop_running_status(&key));
+
+        event_service.stop_block_notify_loop(1);
+        assert!(!event_service.get_event_loop_running_status(&key));
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), stop_receiver.changed())
+                .await
+                .is_ok()
+        );
+    }
+}
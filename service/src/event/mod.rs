@@ -1,5 +1,6 @@
 pub mod event_emitter;
 pub mod event_log_param;
+#[cfg(feature = "channel")]
 pub mod event_service;
 pub mod topic;
 
@@ -93,9 +94,11 @@ mod tests {
         let logs = parse_event_log(&event_logs, "event2", ABI_CONTENT, false).unwrap();
         assert_eq!(expected_logs, logs);
 
-        let event_logs: JSONValue = serde_json::from_str(SM_EVENT_LOGS).unwrap();
-        let logs = parse_event_log(&event_logs, "event2", ABI_CONTENT, true).unwrap();
-        assert_eq!(expected_logs, logs);
+        if cfg!(feature = "sm-crypto") {
+            let event_logs: JSONValue = serde_json::from_str(SM_EVENT_LOGS).unwrap();
+            let logs = parse_event_log(&event_logs, "event2", ABI_CONTENT, true).unwrap();
+            assert_eq!(expected_logs, logs);
+        }
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use keccak_hash::keccak;
 use pad::{Alignment, PadStr};
-use wedpr_l_crypto_hash_sm3::WedprSm3;
-use wedpr_l_utils::traits::Hash;
+
+use crate::sm_crypto::sm3_hash;
 
 pub fn from_integer(value: u64) -> String {
     format!(
@@ -34,17 +34,18 @@ pub fn from_bool(value: bool) -> String {
     }
 }
 
-pub fn from_str(value: &str, sm_crypto: bool) -> String {
+///
+/// 未启用 `sm-crypto` feature 时，`sm_crypto` 为 true 会返回错误信息。
+pub fn from_str(value: &str, sm_crypto: bool) -> Result<String, String> {
     let hash = if sm_crypto {
-        let sm3_hash = WedprSm3::default();
-        sm3_hash.hash(value)
+        sm3_hash(value.as_bytes())?
     } else {
         Vec::from(keccak(value).as_bytes())
     };
-    format!("0x{:}", hex::encode(hash))
+    Ok(format!("0x{:}", hex::encode(hash)))
 }
 
-pub fn from_event_signature(event_signature: &str, sm_crypto: bool) -> String {
+pub fn from_event_signature(event_signature: &str, sm_crypto: bool) -> Result<String, String> {
     let re = fancy_regex::Regex::new(r#"\s+"#).unwrap();
     let event_signature = re.replace_all(event_signature, "");
     from_str(&event_signature, sm_crypto)
@@ -98,31 +99,37 @@ mod tests {
     fn test_from_str() {
         assert_eq!(
             "0x19fa07abe78276c06a4020e2db1604f1ee14b2ebe672d29f51c1c995890a7518",
-            from_str("nanjingboy", false)
-        );
-        assert_eq!(
-            "0x0a7e6ffe38f2f44a895b7652f56317064fbfbe2d22200abf46b22966b63c7fa4",
-            from_str("nanjingboy", true)
+            from_str("nanjingboy", false).unwrap()
         );
+        if cfg!(feature = "sm-crypto") {
+            assert_eq!(
+                "0x0a7e6ffe38f2f44a895b7652f56317064fbfbe2d22200abf46b22966b63c7fa4",
+                from_str("nanjingboy", true).unwrap()
+            );
+        } else {
+            assert!(from_str("nanjingboy", true).is_err());
+        }
     }
 
     #[test]
     fn test_from_event_signature() {
         assert_eq!(
             "0x08ad0c610d1cadcb6ed40e0ed05ad34c51342d4dc96d56a1bf376a64df239789",
-            from_event_signature("event2(string,int256)", false)
+            from_event_signature("event2(string,int256)", false).unwrap()
         );
         assert_eq!(
             "0x08ad0c610d1cadcb6ed40e0ed05ad34c51342d4dc96d56a1bf376a64df239789",
-            from_event_signature(" event2 (string, int256 )", false)
-        );
-        assert_eq!(
-            "0xcc3b390a09af747470093499ab85c9a7e6e71f2fa86951239316edbb4ea3cd5a",
-            from_event_signature("event2(string,int256)", true)
-        );
-        assert_eq!(
-            "0xcc3b390a09af747470093499ab85c9a7e6e71f2fa86951239316edbb4ea3cd5a",
-            from_event_signature("event2 ( string, int256) ", true)
+            from_event_signature(" event2 (string, int256 )", false).unwrap()
         );
+        if cfg!(feature = "sm-crypto") {
+            assert_eq!(
+                "0xcc3b390a09af747470093499ab85c9a7e6e71f2fa86951239316edbb4ea3cd5a",
+                from_event_signature("event2(string,int256)", true).unwrap()
+            );
+            assert_eq!(
+                "0xcc3b390a09af747470093499ab85c9a7e6e71f2fa86951239316edbb4ea3cd5a",
+                from_event_signature("event2 ( string, int256) ", true).unwrap()
+            );
+        }
     }
 }
@@ -0,0 +1,264 @@
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac, NewMac};
+use rand::RngCore;
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::account::{
+    create_account_from_private_key, Account, AccountError, SECP256K1_CURVE_ORDER, SM2_CURVE_ORDER,
+};
+
+///
+/// 命令行派生账户时使用的助记词及助记词密码对应的环境变量。
+pub const MNEMONIC_ENV: &str = "FISCO_BCOS_MNEMONIC";
+pub const MNEMONIC_PASSPHRASE_ENV: &str = "FISCO_BCOS_MNEMONIC_PASSPHRASE";
+
+///
+/// 默认的 BIP44 派生路径前缀（与以太坊钱包一致），完整路径为 `m/44'/60'/0'/0/<index>`。
+pub const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0";
+
+///
+/// BIP32 主密钥的 HMAC 密钥，`SM2` 使用单独的密钥，以免与 `secp256k1` 派生出相同的私钥。
+const SECP256K1_SEED_KEY: &[u8] = b"Bitcoin seed";
+const SM2_SEED_KEY: &[u8] = b"FISCO BCOS SM2 seed";
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+fn create_custom_error(message: String) -> AccountError {
+    AccountError::CustomError { message }
+}
+
+///
+/// 随机生成英文 BIP39 助记词，word_count 可为 12、15、18、21 或 24。
+pub fn generate_mnemonic(word_count: usize) -> Result<String, AccountError> {
+    if ![12, 15, 18, 21, 24].contains(&word_count) {
+        return Err(create_custom_error(format!(
+            "Invalid word count {:}, expected 12, 15, 18, 21 or 24",
+            word_count
+        )));
+    }
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    rand::thread_rng().fill_bytes(&mut entropy);
+    let mnemonic = Mnemonic::from_entropy(&entropy)
+        .map_err(|error| create_custom_error(format!("{:}", error)))?;
+    Ok(mnemonic.to_string())
+}
+
+///
+/// 校验助记词并根据 BIP39 计算种子（64 字节），passphrase 为可选的助记词密码（无密码时为空字符串）。
+pub fn mnemonic_to_seed(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    let mnemonic = Mnemonic::parse(mnemonic)
+        .map_err(|error| create_custom_error(format!("Invalid mnemonic: {:}", error)))?;
+    Ok(Zeroizing::new(mnemonic.to_seed(passphrase).to_vec()))
+}
+
+///
+/// 解析 `m/44'/60'/0'/0/0` 格式的派生路径，硬化索引可使用 `'`、`h` 或 `H` 标记。
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, AccountError> {
+    let invalid_path = || create_custom_error(format!("Invalid derivation path {:?}", path));
+    let mut parts = path.trim().split('/');
+    if parts.next() != Some("m") {
+        return Err(invalid_path());
+    }
+    parts
+        .map(|part| {
+            let (index, hardened) = match part.strip_suffix(&['\'', 'h', 'H'][..]) {
+                Some(index) => (index, true),
+                None => (part, false),
+            };
+            match index.parse::<u32>() {
+                Ok(index) if index < HARDENED_OFFSET => Ok(if hardened {
+                    index + HARDENED_OFFSET
+                } else {
+                    index
+                }),
+                _ => Err(invalid_path()),
+            }
+        })
+        .collect()
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).unwrap();
+    mac.update(data);
+    Zeroizing::new(mac.finalize().into_bytes().to_vec())
+}
+
+///
+/// 计算 (a + b) mod n，a、b 均小于 n（32 字节大端序）。
+fn add_mod(a: &[u8], b: &[u8], n: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut sum = Zeroizing::new(vec![0u8; 32]);
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let value = a[i] as u16 + b[i] as u16 + carry;
+        sum[i] = value as u8;
+        carry = value >> 8;
+    }
+    if carry > 0 || sum[..] >= *n {
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let value = sum[i] as i16 - n[i] as i16 - borrow;
+            sum[i] = value.rem_euclid(256) as u8;
+            borrow = if value < 0 { 1 } else { 0 };
+        }
+    }
+    sum
+}
+
+///
+/// 公钥（64 字节，不含压缩标记）的压缩格式（33 字节）。
+fn compress_public_key(public_key: &[u8]) -> Vec<u8> {
+    let mut compressed_public_key = vec![2 + (public_key[63] & 1)];
+    compressed_public_key.extend_from_slice(&public_key[..32]);
+    compressed_public_key
+}
+
+///
+/// 按 BIP32 根据种子及派生路径派生私钥（32 字节）。
+///
+/// `sm_crypto` 为 true 时使用与 BIP32 相同的派生算法，区别如下：
+///
+/// * 主密钥的 HMAC-SHA512 密钥为 `FISCO BCOS SM2 seed`（`secp256k1` 为 `Bitcoin seed`）。
+/// * 私钥相加时对 `SM2` 曲线的阶取模，非硬化派生时使用 `SM2` 压缩公钥。
+///
+/// 派生出的私钥无效（概率低于 2^-127）时返回错误，而不是像 BIP32 那样跳到下一个索引。
+///
+/// `SM2` 的派生规则为本 SDK 自定义，与 BIP32 钱包及其他 FISCO BCOS 工具（如 Java SDK、WeBASE）
+/// 均不兼容，同一助记词在其他工具中无法恢复出相同的 `SM2` 账户，需自行备份派生出的私钥。
+pub fn derive_private_key(
+    seed: &[u8],
+    path: &str,
+    sm_crypto: bool,
+) -> Result<Zeroizing<Vec<u8>>, AccountError> {
+    let (seed_key, curve_order) = if sm_crypto {
+        (SM2_SEED_KEY, SM2_CURVE_ORDER)
+    } else {
+        (SECP256K1_SEED_KEY, SECP256K1_CURVE_ORDER)
+    };
+    let curve_order = hex::decode(curve_order)?;
+    let invalid_key = || create_custom_error(format!("Derived an invalid key at {:?}", path));
+
+    let master_key = hmac_sha512(seed_key, seed);
+    let mut private_key = Zeroizing::new(master_key[..32].to_vec());
+    let mut chain_code = Zeroizing::new(master_key[32..].to_vec());
+    let mut account =
+        create_account_from_private_key(&private_key, sm_crypto).map_err(|_| invalid_key())?;
+    for index in parse_derivation_path(path)? {
+        let mut data = if index >= HARDENED_OFFSET {
+            let mut data = Zeroizing::new(vec![0u8]);
+            data.extend_from_slice(&private_key);
+            data
+        } else {
+            Zeroizing::new(compress_public_key(&account.public_key))
+        };
+        data.extend_from_slice(&index.to_be_bytes());
+        let child_key = hmac_sha512(&chain_code, &data);
+        if child_key[..32] >= curve_order[..] {
+            return Err(invalid_key());
+        }
+        private_key = add_mod(&child_key[..32], &private_key, &curve_order);
+        chain_code = Zeroizing::new(child_key[32..].to_vec());
+        account =
+            create_account_from_private_key(&private_key, sm_crypto).map_err(|_| invalid_key())?;
+    }
+    Ok(private_key)
+}
+
+///
+/// 根据 BIP39 助记词及派生路径创建账户，`SM2` 的派生规则参见 `derive_private_key`。
+pub fn create_account_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+    path: &str,
+    sm_crypto: bool,
+) -> Result<Account, AccountError> {
+    let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+    create_account_from_private_key(&derive_private_key(&seed, path, sm_crypto)?, sm_crypto)
+}
+
+///
+/// 派生路径 `m/44'/60'/0'/0/<index>` 下从 start 开始的 count 个账户，返回派生路径及账户。
+pub fn derive_accounts(
+    mnemonic: &str,
+    passphrase: &str,
+    start: u32,
+    count: u32,
+    sm_crypto: bool,
+) -> Result<Vec<(String, Account)>, AccountError> {
+    let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+    (start..start.saturating_add(count))
+        .map(|index| {
+            let path = format!("{}/{}", DEFAULT_DERIVATION_PATH_PREFIX, index);
+            let private_key = derive_private_key(&seed, &path, sm_crypto)?;
+            Ok((
+                path,
+                create_account_from_private_key(&private_key, sm_crypto)?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hd_wallet::*;
+
+    #[test]
+    fn test_derive_private_key() {
+        // BIP32 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            hex::encode(derive_private_key(&seed, "m/0'/1", false).unwrap())
+        );
+        assert_eq!(
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            hex::encode(derive_private_key(&seed, "m/0H/1/2H/2/1000000000", false).unwrap())
+        );
+        assert!(derive_private_key(&seed, "0/1", false).is_err());
+        assert!(derive_private_key(&seed, "m/2147483648", false).is_err());
+
+        let mnemonic = "test test test test test test test test test test test junk";
+        let accounts = derive_accounts(mnemonic, "", 0, 2, false).unwrap();
+        assert_eq!("m/44'/60'/0'/0/1", accounts[1].0);
+        assert_eq!(
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            accounts[0].1.get_address()
+        );
+        assert_eq!(
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+            accounts[1].1.get_address()
+        );
+        assert!(mnemonic_to_seed("test test test", "").is_err());
+        assert_eq!(
+            24,
+            generate_mnemonic(24).unwrap().split_whitespace().count()
+        );
+        assert!(generate_mnemonic(13).is_err());
+    }
+
+    #[cfg(feature = "sm-crypto")]
+    #[test]
+    fn test_derive_sm_private_key() {
+        let mnemonic = "test test test test test test test test test test test junk";
+        let accounts = derive_accounts(mnemonic, "", 0, 2, true).unwrap();
+        assert_eq!(
+            "da13d694f2221d39af00abe44245b35a3d6ba5fc9c26245917bf20c7f657dfbe",
+            hex::encode(accounts[0].1.expose_private_key())
+        );
+        assert_eq!(
+            "0xaca3b2be537459ff3f419f55460b3a02c9d1cb2d",
+            accounts[0].1.get_address()
+        );
+        assert_eq!(
+            "243a18c227c0a2a289cd5fcdb9e371bbc7df657049a98fbe50bd59fc24e0e9b2",
+            hex::encode(accounts[1].1.expose_private_key())
+        );
+        assert_eq!(
+            "0xc85d98e59335823b2caf86cddf53989bff31f2ae",
+            accounts[1].1.get_address()
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde_json::Value as JSONValue;
+
+use crate::{
+    account::generate_account,
+    config::{Config, ConfigError, Node},
+};
+
+const DEFAULT_CERT_PATH: &str = "conf";
+const DEFAULT_KEY_STORE_DIR: &str = "account";
+const DEFAULT_ACCOUNT_FILE_FORMAT: &str = "pem";
+
+fn get_str<'a>(value: &'a JSONValue, key: &str) -> Option<&'a str> {
+    value[key].as_str().filter(|value| !value.is_empty())
+}
+
+fn create_custom_error(message: String) -> ConfigError {
+    ConfigError::CustomError { message }
+}
+
+fn parse_peer(peer: &JSONValue) -> Result<Node, ConfigError> {
+    let peer = peer.as_str().unwrap_or_default();
+    let invalid_peer_error = || create_custom_error(format!("Invalid peer {:?}", peer));
+    let (host, port) = peer.rsplit_once(':').ok_or_else(invalid_peer_error)?;
+    Ok(Node {
+        host: host.to_owned(),
+        port: port.parse().map_err(|_| invalid_peer_error())?,
+        priority: 0,
+    })
+}
+
+///
+/// 未设置 `useSMCrypto` 时，根据是否设置了 `enSslCert`、`enSslKey`，
+/// 或 certPath 下是否只有 build_chain 生成的国密证书（`gm/gmca.crt`）来判断是否使用`国密`。
+fn is_sm_crypto(crypto_material: &JSONValue, cert_path: &Path) -> bool {
+    match &crypto_material["useSMCrypto"] {
+        JSONValue::Bool(use_sm_crypto) => *use_sm_crypto,
+        JSONValue::String(use_sm_crypto) => use_sm_crypto.eq_ignore_ascii_case("true"),
+        _ => {
+            get_str(crypto_material, "enSslCert").is_some()
+                || get_str(crypto_material, "enSslKey").is_some()
+                || (!cert_path.join("ca.crt").exists() && cert_path.join("gm/gmca.crt").exists())
+        }
+    }
+}
+
+fn get_cert_file(crypto_material: &JSONValue, key: &str, cert_path: &Path, file: &str) -> String {
+    match get_str(crypto_material, key) {
+        Some(cert_file) => cert_file.to_owned(),
+        None => cert_path.join(file).display().to_string(),
+    }
+}
+
+fn get_account_file(account: &JSONValue, sm_crypto: bool) -> Result<String, ConfigError> {
+    let file_format = get_str(account, "accountFileFormat").unwrap_or(DEFAULT_ACCOUNT_FILE_FORMAT);
+    if file_format != "pem" && file_format != "p12" {
+        return Err(create_custom_error(format!(
+            "The account file format {:?} is not supported, expected `pem` or `p12`",
+            file_format
+        )));
+    }
+    if let Some(account_file_path) = get_str(account, "accountFilePath") {
+        return Ok(account_file_path.to_owned());
+    }
+    Ok(match get_str(account, "accountAddress") {
+        Some(address) => {
+            Path::new(get_str(account, "keyStoreDir").unwrap_or(DEFAULT_KEY_STORE_DIR))
+                .join(if sm_crypto { "gm" } else { "ecdsa" })
+                .join(format!("{:}.{:}", address, file_format))
+                .display()
+                .to_string()
+        }
+        None => String::default(),
+    })
+}
+
+///
+/// 将 Java SDK 的 `config.toml` 转换为配置，base_path 为 Java 应用的工作目录。
+pub fn convert_java_sdk_config(
+    java_config: &JSONValue,
+    base_path: &Path,
+) -> Result<Config, ConfigError> {
+    let crypto_material = &java_config["cryptoMaterial"];
+    let cert_path = get_str(crypto_material, "certPath").unwrap_or(DEFAULT_CERT_PATH);
+    let sm_crypto = is_sm_crypto(crypto_material, &base_path.join(cert_path));
+    let cert_path = Path::new(cert_path);
+    let (ca_cert, sign_cert, sign_key) = if sm_crypto {
+        ("gm/gmca.crt", "gm/gmsdk.crt", "gm/gmsdk.key")
+    } else {
+        ("ca.crt", "sdk.crt", "sdk.key")
+    };
+
+    let mut config = Config {
+        service_type: String::from("channel"),
+        account: get_account_file(&java_config["account"], sm_crypto)?,
+        account_password: get_str(&java_config["account"], "password").map(String::from),
+        sm_crypto,
+        ..Config::default()
+    };
+    config.authentication.ca_cert = get_cert_file(crypto_material, "caCert", cert_path, ca_cert);
+    config.authentication.sign_cert =
+        get_cert_file(crypto_material, "sslCert", cert_path, sign_cert);
+    config.authentication.sign_key = get_cert_file(crypto_material, "sslKey", cert_path, sign_key);
+    if sm_crypto {
+        config.authentication.enc_cert =
+            get_cert_file(crypto_material, "enSslCert", cert_path, "gm/gmensdk.crt");
+        config.authentication.enc_key =
+            get_cert_file(crypto_material, "enSslKey", cert_path, "gm/gmensdk.key");
+    }
+
+    let peers = java_config["network"]["peers"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let mut nodes = peers
+        .iter()
+        .map(parse_peer)
+        .collect::<Result<Vec<Node>, ConfigError>>()?;
+    match nodes.len() {
+        0 => return Err(create_custom_error("network.peers is required".to_owned())),
+        1 => config.node = nodes.remove(0),
+        _ => config.nodes = nodes,
+    }
+    // 与 Java SDK 一致，未设置 accountAddress 及 accountFilePath 时使用随机生成的账户（不会保存到文件）。
+    if config.account.is_empty() {
+        let account = generate_account(sm_crypto).map_err(|err| {
+            create_custom_error(format!("Failed to generate a random account: {:?}", err))
+        })?;
+        config.signer = Some(Arc::new(account));
+    }
+    config.convert_paths(base_path);
+    Ok(config)
+}
+
+///
+/// 根据 Java SDK 的 `config.toml` 创建配置（`channel` 模式），证书路径的对应关系与 Java SDK 相同。
+///
+/// base_path 为 Java 应用的工作目录，配置中的相对路径均相对于该目录，未设置时为配置文件所在目录的上级目录
+///（即 build_chain 生成的 `conf` 目录的上级目录）。
+///
+/// 返回前会调用 `Config::validate` 检查配置。
+pub fn create_config_with_java_sdk_config(
+    config_file_path: &str,
+    base_path: Option<&str>,
+) -> Result<Config, ConfigError> {
+    let config_path = Path::new(config_file_path);
+    let java_config: JSONValue = toml::from_str(&fs::read_to_string(config_path)?)?;
+    let base_path = match base_path {
+        Some(base_path) => PathBuf::from(base_path),
+        None => config_path
+            .parent()
+            .and_then(Path::parent)
+            .map(PathBuf::from)
+            .unwrap_or_default(),
+    };
+    let config = convert_java_sdk_config(&java_config, &base_path)?;
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::Value as JSONValue;
+
+    use crate::java_sdk_config::*;
+
+    #[test]
+    fn test_convert_java_sdk_config() {
+        let java_config: JSONValue = toml::from_str(
+            r#"
+            [cryptoMaterial]
+            certPath = "conf"
+
+            [network]
+            peers = ["127.0.0.1:20200", "127.0.0.1:20201"]
+
+            [account]
+            keyStoreDir = "account"
+            accountAddress = "0x62195d0f77f66c445c4878b845f55d266875705d"
+            accountFileFormat = "pem"
+            "#,
+        )
+        .unwrap();
+        let base_path = Path::new("/opt/app");
+        let config = convert_java_sdk_config(&java_config, base_path).unwrap();
+        assert_eq!("channel", config.service_type);
+        assert!(!config.sm_crypto);
+        assert_eq!(2, config.nodes.len());
+        assert_eq!("127.0.0.1", config.nodes[1].host);
+        assert_eq!(20201, config.nodes[1].port);
+        assert_eq!("/opt/app/conf/ca.crt", config.authentication.ca_cert);
+        assert_eq!("/opt/app/conf/sdk.key", config.authentication.sign_key);
+        assert_eq!(
+            "/opt/app/account/ecdsa/0x62195d0f77f66c445c4878b845f55d266875705d.pem",
+            config.account
+        );
+
+        let java_config: JSONValue = toml::from_str(
+            r#"
+            [cryptoMaterial]
+            certPath = "conf"
+            enSslCert = "conf/gm/gmensdk.crt"
+            enSslKey = "conf/gm/gmensdk.key"
+
+            [network]
+            peers = ["127.0.0.1:20200"]
+
+            [account]
+            accountFilePath = "conf/alice.p12"
+            accountFileFormat = "p12"
+            password = "123456"
+            "#,
+        )
+        .unwrap();
+        let config = convert_java_sdk_config(&java_config, base_path).unwrap();
+        assert!(config.sm_crypto);
+        assert_eq!(20200, config.node.port);
+        assert_eq!("/opt/app/conf/gm/gmca.crt", config.authentication.ca_cert);
+        assert_eq!(
+            "/opt/app/conf/gm/gmensdk.key",
+            config.authentication.enc_key
+        );
+        assert_eq!("/opt/app/conf/alice.p12", config.account);
+        assert_eq!(Some("123456".to_owned()), config.account_password);
+        assert!(config.signer.is_none());
+    }
+
+    #[test]
+    fn test_convert_java_sdk_config_without_account() {
+        let java_config: JSONValue = toml::from_str(
+            r#"
+            [cryptoMaterial]
+            certPath = "conf"
+            useSMCrypto = "false"
+
+            [network]
+            peers = ["127.0.0.1:20200"]
+
+            [account]
+            keyStoreDir = "account"
+            accountFileFormat = "pem"
+            "#,
+        )
+        .unwrap();
+        let base_path = Path::new("/opt/app");
+        let config = convert_java_sdk_config(&java_config, base_path).unwrap();
+        assert!(config.account.is_empty());
+        let signer = config.signer.as_ref().unwrap();
+        assert_eq!(20, signer.address().len());
+        // 每次加载都会生成新的随机账户。
+        let config = convert_java_sdk_config(&java_config, base_path).unwrap();
+        assert_ne!(signer.address(), config.signer.unwrap().address());
+
+        let java_config: JSONValue = toml::from_str(
+            r#"
+            [network]
+            peers = ["127.0.0.1:20200"]
+
+            [account]
+            accountFileFormat = "jks"
+            "#,
+        )
+        .unwrap();
+        assert!(convert_java_sdk_config(&java_config, base_path).is_err());
+    }
+}
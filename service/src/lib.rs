@@ -1,16 +1,34 @@
 pub mod abi;
 pub mod account;
+pub mod account_manager;
+#[cfg(feature = "channel")]
 pub mod channel;
+#[cfg(feature = "channel")]
+pub mod channel_pool;
 pub mod config;
 pub mod event;
+pub mod hd_wallet;
 pub mod helpers;
+pub mod java_sdk_config;
+pub mod keystore;
 pub mod precompiled;
+pub mod signature;
+pub mod signer;
+pub(crate) mod sm_crypto;
+#[cfg(feature = "channel")]
 pub mod tassl;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod transaction;
 pub mod web3;
-pub use config::create_config_with_file;
+pub use config::{
+    create_config_with_env, create_config_with_file, create_config_with_profile,
+    get_config_profiles, ConfigBuilder, ConfigError,
+};
 pub use ethabi;
+#[cfg(feature = "channel")]
 use event::event_service::EventService;
+pub use java_sdk_config::create_config_with_java_sdk_config;
 pub use serde_json;
 pub use web3::service::create_service_with_config as create_web3_service_with_config;
 use web3::service::{Service as Web3Service, ServiceError as Web3ServiceError};
@@ -82,7 +100,15 @@ pub fn create_web3_service(config_file_path: &str) -> Result<Web3Service, Web3Se
 ///    "timeout_seconds": 10
 /// }
 /// ```
-pub fn create_event_service(config_file_path: &str) -> Result<EventService, std::io::Error> {
+#[cfg(feature = "channel")]
+pub fn create_event_service(config_file_path: &str) -> Result<EventService, ConfigError> {
     let config = create_config_with_file(&config_file_path)?;
     Ok(EventService::new(&config))
 }
+
+///
+/// 根据配置创建 event service 服务实例，配置可通过 `ConfigBuilder` 构建。
+#[cfg(feature = "channel")]
+pub fn create_event_service_with_config(config: &config::Config) -> EventService {
+    EventService::new(config)
+}
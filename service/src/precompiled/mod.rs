@@ -4,6 +4,7 @@ pub mod consensus_service;
 pub mod contract_life_cycle_service;
 pub mod permission_service;
 pub mod precompiled_service;
+#[cfg(feature = "cli-support")]
 pub mod sql_service;
 pub mod system_config_service;
 pub mod table_crud_service;
@@ -22,6 +22,7 @@ pub enum PrecompiledServiceError {
     #[error("web3 service error")]
     Web3ServiceError(#[from] Web3ServiceError),
 
+    #[cfg(feature = "cli-support")]
     #[error("sqlparser::parser::ParserError")]
     SQLParserError(#[from] sqlparser::parser::ParserError),
 
@@ -203,11 +204,14 @@ pub(crate) async fn send_transaction(
         web3_service.get_config().sm_crypto,
     )?;
     let tokens = abi.parse_function_tokens(method, &params)?;
-    let transaction_hash = web3_service
-        .send_transaction_with_abi("sendRawTransaction", address, &abi, method, &tokens)
-        .await?;
-    let transaction_receipt = web3_service
-        .get_transaction_receipt_with_timeout(&transaction_hash)
+    let (transaction_hash, transaction_receipt) = web3_service
+        .send_transaction_and_get_receipt_with_abi(
+            "sendRawTransaction",
+            address,
+            &abi,
+            method,
+            &tokens,
+        )
         .await?;
     if transaction_receipt.is_null() {
         return Err(PrecompiledServiceError::CustomError {
@@ -0,0 +1,198 @@
+use keccak_hash::keccak;
+use wedpr_l_crypto_signature_secp256k1::WedprSecp256k1Recover;
+
+use crate::{
+    account::get_address_from_public_key,
+    signer::{Signer, SignerError},
+    sm_crypto::{sm2_verify, sm3_hash},
+};
+
+fn create_custom_error(message: String) -> SignerError {
+    SignerError::CustomError { message }
+}
+
+///
+/// 计算消息哈希，`sm_crypto` 为 true 时使用 SM3，否则使用 Keccak256（与交易哈希的规则一致）。
+pub fn hash_message(message: &[u8], sm_crypto: bool) -> Result<Vec<u8>, SignerError> {
+    if sm_crypto {
+        sm3_hash(message).map_err(create_custom_error)
+    } else {
+        Ok(keccak(message).as_bytes().to_vec())
+    }
+}
+
+///
+/// 对消息哈希（`hash_message`）签名，签名格式与 `Signer::sign_hash` 相同。
+pub async fn sign_message(
+    signer: &(dyn Signer + Send + Sync),
+    message: &[u8],
+    sm_crypto: bool,
+) -> Result<Vec<u8>, SignerError> {
+    signer
+        .sign_hash(&hash_message(message, sm_crypto)?, sm_crypto)
+        .await
+}
+
+///
+/// 校验签名是否由 public_key（64 字节，或带 `0x04` 前缀的 65 字节）对应的私钥对 hash 签名生成。
+/// `secp256k1` 签名为 r、s、v（65 字节，v 可为 0、1、27 或 28），`SM2` 签名为 r、s（64 字节）。
+pub fn verify_signature(public_key: &[u8], hash: &[u8], signature: &[u8], sm_crypto: bool) -> bool {
+    let public_key = match public_key.len() {
+        64 => public_key,
+        65 if public_key[0] == 4 => &public_key[1..],
+        _ => return false,
+    };
+    if sm_crypto {
+        let mut full_public_key = vec![4_u8];
+        full_public_key.extend_from_slice(public_key);
+        signature.len() == 64 && sm2_verify(&full_public_key, hash, signature)
+    } else {
+        match recover_public_key(hash, signature) {
+            Ok(recovered_public_key) => recovered_public_key == public_key,
+            Err(_) => false,
+        }
+    }
+}
+
+///
+/// 校验消息签名，消息哈希的计算规则参见 `hash_message`。
+pub fn verify_message(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+    sm_crypto: bool,
+) -> bool {
+    match hash_message(message, sm_crypto) {
+        Ok(hash) => verify_signature(public_key, &hash, signature, sm_crypto),
+        Err(_) => false,
+    }
+}
+
+///
+/// 根据 `secp256k1` 签名（65 字节，v 可为 0、1、27 或 28）恢复公钥（64 字节）。
+/// `SM2` 签名无法恢复公钥，需使用 `verify_signature` 校验。
+pub fn recover_public_key(hash: &[u8], signature: &[u8]) -> Result<Vec<u8>, SignerError> {
+    if hash.len() != 32 || signature.len() != 65 {
+        return Err(create_custom_error(format!(
+            "Invalid hash or signature length: {:}, {:}",
+            hash.len(),
+            signature.len()
+        )));
+    }
+    let mut signature = signature.to_vec();
+    signature[64] = match signature[64] {
+        0 | 1 => signature[64],
+        27 | 28 => signature[64] - 27,
+        v => return Err(create_custom_error(format!("Invalid signature v: {:}", v))),
+    };
+    let public_key = WedprSecp256k1Recover::default()
+        .recover_public_key(hash, &signature)
+        .map_err(|_| create_custom_error("Failed to recover the public key".to_owned()))?;
+    Ok(match public_key.len() {
+        65 => public_key[1..].to_vec(),
+        _ => public_key,
+    })
+}
+
+///
+/// 根据 `secp256k1` 签名恢复签名者的账户地址（20 字节），与 Solidity 的 `ecrecover` 结果一致。
+pub fn recover_address(hash: &[u8], signature: &[u8]) -> Result<Vec<u8>, SignerError> {
+    get_address_from_public_key(&recover_public_key(hash, signature)?, false)
+        .map_err(|error| create_custom_error(format!("{:?}", error)))
+}
+
+///
+/// 将 `secp256k1` 签名拆分为 Solidity `ecrecover(bytes32 hash, uint8 v, bytes32 r, bytes32 s)`
+/// 所需的 v（27 或 28）、r 及 s。
+pub fn to_ecrecover_params(signature: &[u8]) -> Result<(u8, Vec<u8>, Vec<u8>), SignerError> {
+    if signature.len() != 65 {
+        return Err(create_custom_error(format!(
+            "Invalid signature length: {:}",
+            signature.len()
+        )));
+    }
+    let v = match signature[64] {
+        0 | 1 => signature[64] + 27,
+        27 | 28 => signature[64],
+        v => return Err(create_custom_error(format!("Invalid signature v: {:}", v))),
+    };
+    Ok((v, signature[..32].to_vec(), signature[32..64].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::account::generate_account;
+    use crate::signature::*;
+
+    #[tokio::test]
+    async fn test_sign_message() {
+        let account = generate_account(false).unwrap();
+        let message = b"hello, fisco bcos";
+        let hash = hash_message(message, false).unwrap();
+        let signature = sign_message(&account, message, false).await.unwrap();
+        assert_eq!(account.address, recover_address(&hash, &signature).unwrap());
+        assert!(verify_message(
+            &account.public_key,
+            message,
+            &signature,
+            false
+        ));
+        assert!(!verify_message(
+            &account.public_key,
+            b"hello",
+            &signature,
+            false
+        ));
+
+        let (v, r, s) = to_ecrecover_params(&signature).unwrap();
+        assert!(v == 27 || v == 28);
+        let ecrecover_signature = [r, s, vec![v]].concat();
+        assert_eq!(
+            account.address,
+            recover_address(&hash, &ecrecover_signature).unwrap()
+        );
+        assert!(recover_address(&hash, &signature[..64]).is_err());
+    }
+
+    #[cfg(feature = "sm-crypto")]
+    #[tokio::test]
+    async fn test_sign_sm_message() {
+        let account = generate_account(true).unwrap();
+        let message = b"hello, fisco bcos";
+        let hash = hash_message(message, true).unwrap();
+        assert_ne!(hash_message(message, false).unwrap(), hash);
+        let signature = sign_message(&account, message, true).await.unwrap();
+        assert_eq!(64, signature.len());
+        assert!(verify_message(
+            &account.public_key,
+            message,
+            &signature,
+            true
+        ));
+        assert!(!verify_message(
+            &account.public_key,
+            b"hello",
+            &signature,
+            true
+        ));
+        assert!(!verify_message(
+            &account.public_key,
+            message,
+            &signature,
+            false
+        ));
+
+        let other_account = generate_account(true).unwrap();
+        assert!(!verify_signature(
+            &other_account.public_key,
+            &hash,
+            &signature,
+            true
+        ));
+
+        // SM2 签名无法恢复公钥。
+        assert!(recover_public_key(&hash, &signature).is_err());
+        assert!(recover_address(&hash, &signature).is_err());
+        assert!(to_ecrecover_params(&signature).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use hyper::{client::HttpConnector, Body, Client, Method, Request};
+#[cfg(feature = "rpc")]
+use hyper_rustls::HttpsConnector;
+use serde_json::{json, Value as JSONValue};
+use thiserror::Error;
+use wedpr_l_crypto_signature_secp256k1::WedprSecp256k1Recover;
+use wedpr_l_utils::traits::Signature;
+
+use crate::{
+    account::{get_address_from_public_key, Account},
+    signature::verify_signature,
+    sm_crypto::{is_sm_crypto_enabled, sm2_sign, SM_CRYPTO_DISABLED_MESSAGE},
+};
+#[cfg(feature = "rpc")]
+use crate::{
+    config::RPCConfig,
+    web3::rpc_fetcher::{create_https_connector, create_tls_config},
+};
+
+#[derive(Error, Debug)]
+pub enum SignerError {
+    #[error("hyper::Error")]
+    HyperError(#[from] hyper::Error),
+
+    #[error("hyper::http::Error")]
+    HttpError(#[from] hyper::http::Error),
+
+    #[error("serde_json::Error")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("hex::FromHexError")]
+    FromHexError(#[from] hex::FromHexError),
+
+    #[error("signer custom error")]
+    CustomError { message: String },
+}
+
+fn create_custom_error(message: String) -> SignerError {
+    SignerError::CustomError { message }
+}
+
+///
+/// 交易签名者，私钥可以保存在本地（`Account`）、HSM、远程签名服务（`RemoteSigner`）或系统密钥链中。
+#[async_trait]
+pub trait Signer {
+    ///
+    /// 账户地址（20 字节）。
+    fn address(&self) -> Vec<u8>;
+
+    ///
+    /// 公钥（64 字节，不含压缩标记）。
+    fn public_key(&self) -> Vec<u8>;
+
+    ///
+    /// 对交易哈希签名，返回 r、s（各 32 字节），非国密时另加 1 字节的 recovery id。
+    async fn sign_hash(&self, hash: &[u8], sm_crypto: bool) -> Result<Vec<u8>, SignerError>;
+}
+
+impl fmt::Debug for dyn Signer + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signer(0x{})", hex::encode(self.address()))
+    }
+}
+
+pub(crate) fn sign_hash_with_private_key(
+    private_key: &[u8],
+    hash: &[u8],
+    sm_crypto: bool,
+) -> Result<Vec<u8>, SignerError> {
+    if sm_crypto {
+        sm2_sign(private_key, hash)
+            .ok_or_else(|| create_custom_error(SM_CRYPTO_DISABLED_MESSAGE.to_owned()))
+    } else {
+        WedprSecp256k1Recover::default()
+            .sign(private_key, hash)
+            .map_err(|_| create_custom_error("Failed to sign the hash".to_owned()))
+    }
+}
+
+#[async_trait]
+impl Signer for Account {
+    fn address(&self) -> Vec<u8> {
+        self.address.clone()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign_hash(&self, hash: &[u8], sm_crypto: bool) -> Result<Vec<u8>, SignerError> {
+        sign_hash_with_private_key(self.expose_private_key(), hash, sm_crypto)
+    }
+}
+
+#[cfg(feature = "rpc")]
+type RemoteSignerClient = Client<HttpsConnector<HttpConnector>>;
+#[cfg(not(feature = "rpc"))]
+type RemoteSignerClient = Client<HttpConnector>;
+
+///
+/// 启用 `rpc` feature 时使用与 `RPCFetcher` 相同的 rustls 连接器（使用 webpki 根证书），支持 `https://`，
+/// 否则仅支持 `http://`。
+#[cfg(feature = "rpc")]
+fn create_remote_signer_client() -> Result<RemoteSignerClient, SignerError> {
+    let tls_config = create_tls_config(&RPCConfig::default())
+        .map_err(|error| create_custom_error(format!("{:?}", error)))?;
+    Ok(Client::builder().build(create_https_connector(tls_config)))
+}
+
+#[cfg(not(feature = "rpc"))]
+fn create_remote_signer_client() -> Result<RemoteSignerClient, SignerError> {
+    Ok(Client::new())
+}
+
+///
+/// 通过 HTTP(S) 调用远程签名服务（私钥不离开签名服务），接口如下：
+///
+/// * `GET <url>`：返回 `{"public_key": "0x..."}`。
+/// * `POST <url>`：请求为 `{"hash": "0x...", "sm_crypto": false}`，返回 `{"signature": "0x..."}`，
+///   签名格式与 `Signer::sign_hash` 相同。
+///
+/// 签名服务返回的签名会使用公钥校验后再使用。
+pub struct RemoteSigner {
+    url: String,
+    public_key: Vec<u8>,
+    address: Vec<u8>,
+    sm_crypto: bool,
+    bearer_token: Option<String>,
+    client: RemoteSignerClient,
+}
+
+fn decode_hex_field(value: &JSONValue, key: &str) -> Result<Vec<u8>, SignerError> {
+    match value[key].as_str() {
+        Some(field) => Ok(hex::decode(field.trim_start_matches("0x"))?),
+        None => Err(create_custom_error(format!(
+            "The remote signer response has no {:?}",
+            key
+        ))),
+    }
+}
+
+async fn request_remote_signer(
+    client: &RemoteSignerClient,
+    url: &str,
+    bearer_token: Option<&str>,
+    method: Method,
+    body: Body,
+) -> Result<JSONValue, SignerError> {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(url)
+        .header("content-type", "application/json");
+    if let Some(bearer_token) = bearer_token {
+        builder = builder.header("authorization", format!("Bearer {}", bearer_token));
+    }
+    let response = client.request(builder.body(body)?).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    if !status.is_success() {
+        return Err(create_custom_error(format!(
+            "The remote signer responded with {}: {}",
+            status,
+            String::from_utf8_lossy(&body)
+        )));
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+impl RemoteSigner {
+    ///
+    /// public_key 为签名服务所持私钥对应的公钥（64 字节，或带 `0x04` 前缀的 65 字节）。
+    pub fn new(url: &str, public_key: &[u8], sm_crypto: bool) -> Result<RemoteSigner, SignerError> {
+        if sm_crypto && !is_sm_crypto_enabled() {
+            return Err(create_custom_error(SM_CRYPTO_DISABLED_MESSAGE.to_owned()));
+        }
+        let public_key = match public_key.len() {
+            64 => public_key.to_vec(),
+            65 if public_key[0] == 4 => public_key[1..].to_vec(),
+            _ => {
+                return Err(create_custom_error(format!(
+                    "Invalid public key length: {:?}",
+                    public_key.len()
+                )))
+            }
+        };
+        let address = get_address_from_public_key(&public_key, sm_crypto)
+            .map_err(|error| create_custom_error(format!("{:?}", error)))?;
+        Ok(RemoteSigner {
+            url: url.to_owned(),
+            address,
+            public_key,
+            sm_crypto,
+            bearer_token: None,
+            client: create_remote_signer_client()?,
+        })
+    }
+
+    ///
+    /// 从签名服务获取公钥并创建 `RemoteSigner`。
+    pub async fn connect(
+        url: &str,
+        bearer_token: Option<&str>,
+        sm_crypto: bool,
+    ) -> Result<RemoteSigner, SignerError> {
+        let response = request_remote_signer(
+            &create_remote_signer_client()?,
+            url,
+            bearer_token,
+            Method::GET,
+            Body::empty(),
+        )
+        .await?;
+        let signer =
+            RemoteSigner::new(url, &decode_hex_field(&response, "public_key")?, sm_crypto)?;
+        Ok(match bearer_token {
+            Some(bearer_token) => signer.bearer_token(bearer_token),
+            None => signer,
+        })
+    }
+
+    ///
+    /// 请求签名服务时使用的 `Authorization: Bearer <token>`。
+    pub fn bearer_token(mut self, bearer_token: &str) -> RemoteSigner {
+        self.bearer_token = Some(bearer_token.to_owned());
+        self
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn address(&self) -> Vec<u8> {
+        self.address.clone()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign_hash(&self, hash: &[u8], sm_crypto: bool) -> Result<Vec<u8>, SignerError> {
+        if sm_crypto != self.sm_crypto {
+            return Err(create_custom_error(format!(
+                "The remote signer is created with sm_crypto = {:}",
+                self.sm_crypto
+            )));
+        }
+        let body = json!({
+            "hash": format!("0x{}", hex::encode(hash)),
+            "sm_crypto": sm_crypto,
+        });
+        let response = request_remote_signer(
+            &self.client,
+            &self.url,
+            self.bearer_token.as_deref(),
+            Method::POST,
+            Body::from(body.to_string()),
+        )
+        .await?;
+        let signature = decode_hex_field(&response, "signature")?;
+        if !verify_signature(&self.public_key, hash, &signature, sm_crypto) {
+            return Err(create_custom_error(
+                "The remote signer returned an invalid signature".to_owned(),
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::account::generate_account;
+    use crate::signer::*;
+    use crate::testing::mock_signer::MockSigner;
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let account = generate_account(false).unwrap();
+        let address = account.address.clone();
+        let public_key = account.public_key.clone();
+        let mock_signer = MockSigner::start(account, false).await.unwrap();
+        mock_signer.set_bearer_token("token");
+
+        let hash = [1_u8; 32];
+        assert!(RemoteSigner::connect(&mock_signer.url(), None, false)
+            .await
+            .is_err());
+        let signer = RemoteSigner::connect(&mock_signer.url(), Some("token"), false)
+            .await
+            .unwrap();
+        assert_eq!(address, signer.address());
+        let signature = signer.sign_hash(&hash, false).await.unwrap();
+        assert!(verify_signature(&public_key, &hash, &signature, false));
+        assert_eq!(1, mock_signer.get_sign_count());
+        assert!(signer.sign_hash(&hash, true).await.is_err());
+
+        let other_public_key = generate_account(false).unwrap().public_key;
+        let signer = RemoteSigner::new(&mock_signer.url(), &other_public_key, false)
+            .unwrap()
+            .bearer_token("token");
+        assert!(signer.sign_hash(&hash, false).await.is_err());
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(feature = "sm-crypto")]
+use wedpr_l_crypto_hash_sm3::WedprSm3;
+#[cfg(feature = "sm-crypto")]
+use wedpr_l_crypto_signature_sm2::WedprSm2p256v1;
+#[cfg(feature = "sm-crypto")]
+use wedpr_l_libsm::sm2::signature::SigCtx;
+#[cfg(feature = "sm-crypto")]
+use wedpr_l_utils::traits::{Hash, Signature};
+
+pub(crate) const SM_CRYPTO_DISABLED_MESSAGE: &str =
+    "The `sm-crypto` feature is disabled, please enable it to use sm crypto";
+
+pub(crate) fn is_sm_crypto_enabled() -> bool {
+    cfg!(feature = "sm-crypto")
+}
+
+///
+/// 未启用 `sm-crypto` feature 时返回 `SM_CRYPTO_DISABLED_MESSAGE` 错误信息。
+#[cfg(feature = "sm-crypto")]
+pub(crate) fn sm3_hash(data: &[u8]) -> Result<Vec<u8>, String> {
+    Ok(WedprSm3::default().hash(data))
+}
+
+#[cfg(not(feature = "sm-crypto"))]
+pub(crate) fn sm3_hash(_data: &[u8]) -> Result<Vec<u8>, String> {
+    Err(SM_CRYPTO_DISABLED_MESSAGE.to_owned())
+}
+
+#[cfg(feature = "sm-crypto")]
+pub(crate) fn sm2_sign(private_key: &[u8], message: &[u8]) -> Option<Vec<u8>> {
+    WedprSm2p256v1::default().sign(private_key, message).ok()
+}
+
+#[cfg(not(feature = "sm-crypto"))]
+pub(crate) fn sm2_sign(_private_key: &[u8], _message: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+///
+/// public_key 为带 `0x04` 前缀的未压缩公钥。
+#[cfg(feature = "sm-crypto")]
+pub(crate) fn sm2_verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    WedprSm2p256v1::default().verify(public_key, message, signature)
+}
+
+#[cfg(not(feature = "sm-crypto"))]
+pub(crate) fn sm2_verify(_public_key: &[u8], _message: &[u8], _signature: &[u8]) -> bool {
+    false
+}
+
+///
+/// 返回带 `0x04` 前缀的未压缩公钥。
+#[cfg(feature = "sm-crypto")]
+pub(crate) fn sm2_derive_public_key(private_key: &[u8]) -> Option<Vec<u8>> {
+    let sig_ctx = SigCtx::new();
+    let secret_key = sig_ctx.load_seckey(private_key).ok()?;
+    let public_key = sig_ctx.pk_from_sk(&secret_key);
+    Some(sig_ctx.serialize_pubkey(&public_key, false))
+}
+
+#[cfg(not(feature = "sm-crypto"))]
+pub(crate) fn sm2_derive_public_key(_private_key: &[u8]) -> Option<Vec<u8>> {
+    None
+}
@@ -22,6 +22,9 @@ pub(crate) type tls_session_secret_cb_fn = Option<
 pub(crate) type GEN_SESSION_CB =
     Option<unsafe extern "C" fn(*const SSL, *mut c_uchar, *mut c_uint) -> c_int>;
 
+pub(crate) type pem_password_cb =
+    Option<unsafe extern "C" fn(*mut c_char, c_int, c_int, *mut c_void) -> c_int>;
+
 pub(crate) type bio_info_cb =
     Option<unsafe extern "C" fn(*mut BIO, c_int, *const c_char, c_int, c_long, c_long)>;
 
@@ -45,6 +48,10 @@ pub(crate) enum SSL_METHOD {}
 
 pub(crate) enum X509_STORE_CTX {}
 
+pub(crate) enum X509_STORE {}
+
+pub(crate) enum EVP_PKEY {}
+
 pub(crate) const CRYPTO_LOCK: c_int = 1;
 
 pub(crate) const X509_FILETYPE_PEM: c_int = 1;
@@ -53,12 +60,26 @@ pub(crate) const SSL_FILETYPE_PEM: c_int = X509_FILETYPE_PEM;
 
 pub(crate) const SSL_CTRL_MODE: c_int = 33;
 
+pub(crate) const SSL_CTRL_EXTRA_CHAIN_CERT: c_int = 14;
+
+pub(crate) const SSL_MODE_ENABLE_PARTIAL_WRITE: c_long = 0x1;
+
+pub(crate) const SSL_MODE_ACCEPT_MOVING_WRITE_BUFFER: c_long = 0x2;
+
 pub(crate) const SSL_MODE_AUTO_RETRY: c_long = 0x4;
 
 pub(crate) const SSL_VERIFY_PEER: c_int = 1;
 
 pub(crate) const SSL_VERIFY_FAIL_IF_NO_PEER_CERT: c_int = 2;
 
+pub(crate) const SSL_ERROR_WANT_READ: c_int = 2;
+
+pub(crate) const SSL_ERROR_WANT_WRITE: c_int = 3;
+
+pub(crate) const SSL_ERROR_SYSCALL: c_int = 5;
+
+pub(crate) const SSL_ERROR_ZERO_RETURN: c_int = 6;
+
 #[cfg(not(ossl110))]
 pub(crate) const SSL_MAX_SID_CTX_LENGTH: c_int = 32;
 
@@ -721,6 +742,10 @@ extern "C" {
         file: *const c_char,
         file_type: c_int,
     ) -> c_int;
+    pub(crate) fn SSL_CTX_use_certificate(ctx: *mut SSL_CTX, x509: *mut X509) -> c_int;
+    pub(crate) fn SSL_CTX_use_PrivateKey(ctx: *mut SSL_CTX, pkey: *mut EVP_PKEY) -> c_int;
+    pub(crate) fn SSL_CTX_use_enc_PrivateKey(ctx: *mut SSL_CTX, pkey: *mut EVP_PKEY) -> c_int;
+    pub(crate) fn SSL_CTX_get_cert_store(ctx: *const SSL_CTX) -> *mut X509_STORE;
     pub(crate) fn SSL_CTX_check_private_key(ctx: *mut SSL_CTX) -> c_int;
     pub(crate) fn SSL_CTX_check_enc_private_key(ctx: *mut SSL_CTX) -> c_int;
 
@@ -734,16 +759,52 @@ extern "C" {
     pub(crate) fn SSL_new(ctx: *mut SSL_CTX) -> *mut SSL;
     pub(crate) fn SSL_shutdown(ssl: *mut SSL) -> c_int;
     pub(crate) fn SSL_free(ssl: *mut SSL);
+    pub(crate) fn SSL_ctrl(ssl: *mut SSL, cmd: c_int, larg: c_long, parg: *mut c_void) -> c_long;
+    pub(crate) fn SSL_set_fd(ssl: *mut SSL, fd: c_int) -> c_int;
 
     pub(crate) fn BIO_new_connect(host_port: *const c_char) -> *mut BIO;
+    pub(crate) fn BIO_new_mem_buf(buf: *const c_void, len: c_int) -> *mut BIO;
+    pub(crate) fn BIO_free(bio: *mut BIO) -> c_int;
+
+    pub(crate) fn PEM_read_bio_X509(
+        bio: *mut BIO,
+        x509: *mut *mut X509,
+        callback: pem_password_cb,
+        user_data: *mut c_void,
+    ) -> *mut X509;
+    pub(crate) fn PEM_read_bio_PrivateKey(
+        bio: *mut BIO,
+        pkey: *mut *mut EVP_PKEY,
+        callback: pem_password_cb,
+        user_data: *mut c_void,
+    ) -> *mut EVP_PKEY;
+    pub(crate) fn X509_free(x509: *mut X509);
+    pub(crate) fn X509_STORE_add_cert(store: *mut X509_STORE, x509: *mut X509) -> c_int;
+    pub(crate) fn EVP_PKEY_free(pkey: *mut EVP_PKEY);
+    pub(crate) fn ERR_clear_error();
+
     pub(crate) fn SSL_set_bio(ssl: *mut SSL, read_bio: *mut BIO, write_bio: *mut BIO);
     pub(crate) fn SSL_set_connect_state(ssl: *mut SSL);
     pub(crate) fn SSL_do_handshake(ssl: *mut SSL) -> c_int;
     pub(crate) fn SSL_write(ssl: *mut SSL, buf: *const c_void, num: c_int) -> c_int;
     pub(crate) fn SSL_read(ssl: *mut SSL, buf: *mut c_void, num: c_int) -> c_int;
     pub(crate) fn SSL_clear(ssl: *mut SSL) -> c_int;
+    pub(crate) fn SSL_get_error(ssl: *const SSL, ret: c_int) -> c_int;
+
+    #[cfg(test)]
+    pub(crate) fn TLSv1_2_server_method() -> *const SSL_METHOD;
+    #[cfg(test)]
+    pub(crate) fn SSL_accept(ssl: *mut SSL) -> c_int;
 }
 
 pub(crate) unsafe fn SSL_CTX_set_mode(ctx: *mut SSL_CTX, op: c_long) -> c_long {
     SSL_CTX_ctrl(ctx, SSL_CTRL_MODE, op, ptr::null_mut())
 }
+
+pub(crate) unsafe fn SSL_CTX_add_extra_chain_cert(ctx: *mut SSL_CTX, x509: *mut X509) -> c_long {
+    SSL_CTX_ctrl(ctx, SSL_CTRL_EXTRA_CHAIN_CERT, 0, x509 as *mut c_void)
+}
+
+pub(crate) unsafe fn SSL_set_mode(ssl: *mut SSL, op: c_long) -> c_long {
+    SSL_ctrl(ssl, SSL_CTRL_MODE, op, ptr::null_mut())
+}
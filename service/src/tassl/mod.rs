@@ -1,4 +1,5 @@
 mod ffi;
+pub mod stream;
 
 use std::{
     cell::RefCell,
@@ -18,6 +19,9 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum TASSLError {
+    #[error("std::io::Error")]
+    StdIOError(#[from] std::io::Error),
+
     #[error("std::ffi::NulError")]
     FFINulError(#[from] std::ffi::NulError),
 
@@ -28,12 +32,52 @@ pub enum TASSLError {
     CustomError { message: String },
 }
 
+#[derive(Debug, PartialEq)]
+pub enum TASSLIOStatus {
+    Done(usize),
+    WantRead,
+    WantWrite,
+}
+
+///
+/// 持有内存 BIO，drop 时释放。
+struct MemBIO(*mut BIO);
+
+impl MemBIO {
+    unsafe fn new(data: &[u8]) -> Result<MemBIO, TASSLError> {
+        if data.len() > c_int::MAX as usize {
+            return Err(TASSLError::CustomError {
+                message: "The pem content is too large".to_owned(),
+            });
+        }
+        let bio = BIO_new_mem_buf(data.as_ptr() as *const c_void, data.len() as c_int);
+        if bio.is_null() {
+            Err(TASSLError::CustomError {
+                message: "BIO_new_mem_buf invoked failed".to_owned(),
+            })
+        } else {
+            Ok(MemBIO(bio))
+        }
+    }
+}
+
+impl Drop for MemBIO {
+    fn drop(&mut self) {
+        unsafe {
+            BIO_free(self.0);
+        }
+    }
+}
+
 pub struct TASSL {
     ctx: RefCell<Option<*mut SSL_CTX>>,
     ssl: RefCell<Option<*mut SSL>>,
     timeout_seconds: i64,
 }
 
+// TASSL 独占其持有的 SSL_CTX 及 SSL 对象，不存在跨线程的共享访问，可安全地在线程间转移。
+unsafe impl Send for TASSL {}
+
 impl TASSL {
     fn parse_ffi_invoke_result(&self, code: c_int, message: &str) -> Result<c_int, TASSLError> {
         if code <= 0 {
@@ -46,6 +90,27 @@ impl TASSL {
         }
     }
 
+    fn parse_ffi_non_blocking_invoke_result(
+        &self,
+        ssl: *mut SSL,
+        code: c_int,
+        message: &str,
+    ) -> Result<TASSLIOStatus, TASSLError> {
+        if code > 0 {
+            return Ok(TASSLIOStatus::Done(code as usize));
+        }
+        match unsafe { SSL_get_error(ssl, code) } {
+            SSL_ERROR_WANT_READ => Ok(TASSLIOStatus::WantRead),
+            SSL_ERROR_WANT_WRITE => Ok(TASSLIOStatus::WantWrite),
+            SSL_ERROR_ZERO_RETURN => Ok(TASSLIOStatus::Done(0)),
+            SSL_ERROR_SYSCALL if code == 0 => Ok(TASSLIOStatus::Done(0)),
+            error_code => Err(TASSLError::ServiceError {
+                code: error_code,
+                message: message.to_owned(),
+            }),
+        }
+    }
+
     pub fn new(timeout_seconds: i64) -> TASSL {
         TASSL {
             ctx: RefCell::new(None),
@@ -113,6 +178,165 @@ impl TASSL {
         })
     }
 
+    unsafe fn get_or_create_ctx(&self) -> *mut SSL_CTX {
+        if self.ctx.borrow().is_none() {
+            let ctx = SSL_CTX_new(TLSv1_2_client_method());
+            SSL_CTX_set_timeout(ctx, self.timeout_seconds);
+            SSL_CTX_set_mode(ctx, SSL_MODE_AUTO_RETRY);
+            SSL_CTX_set_verify(ctx, SSL_VERIFY_PEER | SSL_VERIFY_FAIL_IF_NO_PEER_CERT, None);
+            SSL_CTX_set_verify_depth(ctx, 10);
+            *self.ctx.borrow_mut() = Some(ctx);
+        }
+        self.ctx.borrow().unwrap()
+    }
+
+    unsafe fn check_private_keys(
+        &self,
+        ctx: *mut SSL_CTX,
+        check_enc_private_key: bool,
+    ) -> Result<(), TASSLError> {
+        self.parse_ffi_invoke_result(
+            SSL_CTX_check_private_key(ctx),
+            "SSL_CTX_check_private_key invoked failed",
+        )?;
+        if check_enc_private_key {
+            self.parse_ffi_invoke_result(
+                SSL_CTX_check_enc_private_key(ctx),
+                "SSL_CTX_check_enc_private_key invoked failed",
+            )?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// 读取 pem 内容中的所有证书，调用方需负责释放返回的证书。
+    unsafe fn read_pem_certificates(
+        &self,
+        pem: &[u8],
+        name: &str,
+    ) -> Result<Vec<*mut X509>, TASSLError> {
+        let bio = MemBIO::new(pem)?;
+        let mut certificates = vec![];
+        loop {
+            let certificate = PEM_read_bio_X509(bio.0, ptr::null_mut(), None, ptr::null_mut());
+            if certificate.is_null() {
+                break;
+            }
+            certificates.push(certificate);
+        }
+        // 读取到末尾时会产生 PEM_R_NO_START_LINE 错误，需清除以免影响后续调用。
+        ERR_clear_error();
+        if certificates.is_empty() {
+            Err(TASSLError::ServiceError {
+                code: 0,
+                message: format!("PEM_read_bio_X509 invoked failed. {:}", name),
+            })
+        } else {
+            Ok(certificates)
+        }
+    }
+
+    ///
+    /// 读取 pem 内容中的私钥，调用方需负责释放返回的私钥。
+    unsafe fn read_pem_private_key(
+        &self,
+        pem: &[u8],
+        name: &str,
+    ) -> Result<*mut EVP_PKEY, TASSLError> {
+        let bio = MemBIO::new(pem)?;
+        let private_key = PEM_read_bio_PrivateKey(bio.0, ptr::null_mut(), None, ptr::null_mut());
+        if private_key.is_null() {
+            Err(TASSLError::ServiceError {
+                code: 0,
+                message: format!("PEM_read_bio_PrivateKey invoked failed. {:}", name),
+            })
+        } else {
+            Ok(private_key)
+        }
+    }
+
+    ///
+    /// 使用 pem 内容中的第一个证书作为 ctx 的证书，sign 为 true 时其余证书将作为证书链。
+    unsafe fn use_pem_certificates(
+        &self,
+        ctx: *mut SSL_CTX,
+        pem: &[u8],
+        name: &str,
+        sign: bool,
+    ) -> Result<(), TASSLError> {
+        let mut certificates = self.read_pem_certificates(pem, name)?.into_iter();
+        let certificate = certificates.next().unwrap();
+        let result = self.parse_ffi_invoke_result(
+            SSL_CTX_use_certificate(ctx, certificate),
+            &format!("SSL_CTX_use_certificate invoked failed. {:}", name),
+        );
+        X509_free(certificate);
+        if result.is_err() || !sign {
+            certificates.for_each(|certificate| X509_free(certificate));
+            return result.map(|_| ());
+        }
+        while let Some(certificate) = certificates.next() {
+            // 调用成功后证书由 ctx 持有，无需释放。
+            if SSL_CTX_add_extra_chain_cert(ctx, certificate) <= 0 {
+                X509_free(certificate);
+                certificates.for_each(|certificate| X509_free(certificate));
+                return Err(TASSLError::ServiceError {
+                    code: 0,
+                    message: format!("SSL_CTX_add_extra_chain_cert invoked failed. {:}", name),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// 与 `load_auth_files` 相同，但证书及私钥为内存中的 pem 内容。
+    pub fn load_auth_bytes(
+        &self,
+        ca_cert: &[u8],
+        sign_key: &[u8],
+        sign_cert: &[u8],
+        enc_key: &[u8],
+        enc_cert: &[u8],
+    ) -> Result<(), TASSLError> {
+        unsafe {
+            let ctx = self.get_or_create_ctx();
+            let store = SSL_CTX_get_cert_store(ctx);
+            let mut result = Ok(0);
+            for certificate in self.read_pem_certificates(ca_cert, "Ca Cert")? {
+                if result.is_ok() {
+                    result = self.parse_ffi_invoke_result(
+                        X509_STORE_add_cert(store, certificate),
+                        "X509_STORE_add_cert invoked failed. Ca Cert",
+                    );
+                }
+                X509_free(certificate);
+            }
+            result?;
+            self.use_pem_certificates(ctx, sign_cert, "Sign Cert", true)?;
+            let private_key = self.read_pem_private_key(sign_key, "Sign Key")?;
+            let result = self.parse_ffi_invoke_result(
+                SSL_CTX_use_PrivateKey(ctx, private_key),
+                "SSL_CTX_use_PrivateKey invoked failed. Sign Key",
+            );
+            EVP_PKEY_free(private_key);
+            result?;
+            let mut check_enc_private_key = false;
+            if !enc_cert.is_empty() && !enc_key.is_empty() {
+                self.use_pem_certificates(ctx, enc_cert, "Enc Cert", false)?;
+                let private_key = self.read_pem_private_key(enc_key, "Enc Key")?;
+                let result = self.parse_ffi_invoke_result(
+                    SSL_CTX_use_enc_PrivateKey(ctx, private_key),
+                    "SSL_CTX_use_enc_PrivateKey invoked failed. Enc Key",
+                );
+                EVP_PKEY_free(private_key);
+                result?;
+                check_enc_private_key = true;
+            }
+            self.check_private_keys(ctx, check_enc_private_key)
+        }
+    }
+
     pub fn load_auth_files(
         &self,
         ca_cert_file: &str,
@@ -122,15 +346,7 @@ impl TASSL {
         enc_cert_file: &str,
     ) -> Result<(), TASSLError> {
         unsafe {
-            if self.ctx.borrow().is_none() {
-                let ctx = SSL_CTX_new(TLSv1_2_client_method());
-                SSL_CTX_set_timeout(ctx, self.timeout_seconds);
-                SSL_CTX_set_mode(ctx, SSL_MODE_AUTO_RETRY);
-                SSL_CTX_set_verify(ctx, SSL_VERIFY_PEER | SSL_VERIFY_FAIL_IF_NO_PEER_CERT, None);
-                SSL_CTX_set_verify_depth(ctx, 10);
-                *self.ctx.borrow_mut() = Some(ctx);
-            }
-            let ctx = self.ctx.borrow().unwrap();
+            let ctx = self.get_or_create_ctx();
             self.parse_ffi_invoke_result(
                 SSL_CTX_load_verify_locations(
                     ctx,
@@ -189,17 +405,7 @@ impl TASSL {
                 )?;
                 check_enc_private_key = true;
             }
-            self.parse_ffi_invoke_result(
-                SSL_CTX_check_private_key(ctx),
-                "SSL_CTX_check_private_key invoked failed",
-            )?;
-            if check_enc_private_key {
-                self.parse_ffi_invoke_result(
-                    SSL_CTX_check_enc_private_key(ctx),
-                    "SSL_CTX_check_enc_private_key invoked failed",
-                )?;
-            }
-            Ok(())
+            self.check_private_keys(ctx, check_enc_private_key)
         }
     }
 
@@ -227,6 +433,33 @@ impl TASSL {
         }
     }
 
+    ///
+    /// 将已建立连接的非阻塞 socket 交由 TASSL 接管，之后需通过 `try_handshake` 完成握手，
+    /// 并只能通过 `try_read` 及 `try_write` 进行读写。
+    pub fn attach_socket(&self, fd: c_int) -> Result<(), TASSLError> {
+        unsafe {
+            if self.ssl.borrow().is_none() {
+                *self.ssl.borrow_mut() = Some(SSL_new(self.ctx.borrow().unwrap()));
+            }
+            let ssl = self.ssl.borrow().unwrap();
+            self.parse_ffi_invoke_result(SSL_set_fd(ssl, fd), "SSL_set_fd invoked failed")?;
+            SSL_set_mode(
+                ssl,
+                SSL_MODE_ENABLE_PARTIAL_WRITE | SSL_MODE_ACCEPT_MOVING_WRITE_BUFFER,
+            );
+            SSL_set_connect_state(ssl);
+            Ok(())
+        }
+    }
+
+    pub fn try_handshake(&self) -> Result<TASSLIOStatus, TASSLError> {
+        unsafe {
+            let ssl = self.ssl.borrow().unwrap();
+            let code = SSL_do_handshake(ssl);
+            self.parse_ffi_non_blocking_invoke_result(ssl, code, "SSL_do_handshake invoked failed")
+        }
+    }
+
     pub fn write(&self, buf: &[u8]) -> Result<usize, TASSLError> {
         if buf.is_empty() {
             return Ok(0);
@@ -268,6 +501,30 @@ impl TASSL {
         }
     }
 
+    pub fn try_write(&self, buf: &[u8]) -> Result<TASSLIOStatus, TASSLError> {
+        if buf.is_empty() {
+            return Ok(TASSLIOStatus::Done(0));
+        }
+        let len = cmp::min(c_int::MAX as usize, buf.len()) as c_int;
+        unsafe {
+            let ssl = self.ssl.borrow().unwrap();
+            let code = SSL_write(ssl, buf.as_ptr() as *const c_void, len);
+            self.parse_ffi_non_blocking_invoke_result(ssl, code, "SSL_write invoked failed")
+        }
+    }
+
+    pub fn try_read(&self, buf: &mut [u8]) -> Result<TASSLIOStatus, TASSLError> {
+        if buf.is_empty() {
+            return Ok(TASSLIOStatus::Done(0));
+        }
+        let len = cmp::min(c_int::MAX as usize, buf.len()) as c_int;
+        unsafe {
+            let ssl = self.ssl.borrow().unwrap();
+            let code = SSL_read(ssl, buf.as_ptr() as *mut c_void, len);
+            self.parse_ffi_non_blocking_invoke_result(ssl, code, "SSL_read invoked failed")
+        }
+    }
+
     pub fn close(&self) {
         unsafe {
             let ssl = self.ssl.borrow();
@@ -0,0 +1,245 @@
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use libc::c_int;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, Interest, ReadBuf},
+    net::TcpStream,
+};
+
+use crate::tassl::{TASSLError, TASSLIOStatus, TASSL};
+
+fn to_io_error(err: TASSLError) -> io::Error {
+    match err {
+        TASSLError::StdIOError(err) => err,
+        err => io::Error::other(err),
+    }
+}
+
+///
+/// 基于 tokio 非阻塞 socket 的 TASSL 连接，读写由 SSL 返回的 WANT_READ/WANT_WRITE 驱动，
+/// 等待期间不会阻塞运行时。
+pub struct TASSLStream {
+    // tassl 须先于 tcp_stream 释放，以保证 SSL 对象释放时底层 socket 仍然有效。
+    tassl: TASSL,
+    tcp_stream: TcpStream,
+}
+
+impl TASSLStream {
+    fn poll_tassl_io<F>(&self, cx: &mut Context<'_>, mut op: F) -> Poll<Result<usize, TASSLError>>
+    where
+        F: FnMut(&TASSL) -> Result<TASSLIOStatus, TASSLError>,
+    {
+        let mut status = op(&self.tassl)?;
+        loop {
+            let interest = match status {
+                TASSLIOStatus::Done(size) => return Poll::Ready(Ok(size)),
+                TASSLIOStatus::WantRead => Interest::READABLE,
+                TASSLIOStatus::WantWrite => Interest::WRITABLE,
+            };
+            let ready = if interest.is_readable() {
+                self.tcp_stream.poll_read_ready(cx)
+            } else {
+                self.tcp_stream.poll_write_ready(cx)
+            };
+            match ready {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                Poll::Ready(Ok(_)) => {}
+            }
+            // 仍需等待同一事件时返回 WouldBlock，以清除 tokio 记录的就绪状态，下次 poll 时重新注册 waker。
+            let result = self.tcp_stream.try_io(interest, || match op(&self.tassl) {
+                Ok(TASSLIOStatus::WantRead) if interest.is_readable() => {
+                    Err(io::ErrorKind::WouldBlock.into())
+                }
+                Ok(TASSLIOStatus::WantWrite) if interest.is_writable() => {
+                    Err(io::ErrorKind::WouldBlock.into())
+                }
+                result => Ok(result),
+            });
+            status = match result {
+                Ok(result) => result?,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => status,
+                Err(err) => return Poll::Ready(Err(err.into())),
+            };
+        }
+    }
+
+    pub async fn connect(tassl: TASSL, host: &str, port: i32) -> Result<TASSLStream, TASSLError> {
+        let tcp_stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+        #[cfg(unix)]
+        let fd = {
+            use std::os::unix::io::AsRawFd;
+            tcp_stream.as_raw_fd() as c_int
+        };
+        // SSL_set_fd 只接收 c_int，SOCKET 超出范围时不能截断。
+        #[cfg(windows)]
+        let fd = {
+            use std::{convert::TryFrom, os::windows::io::AsRawSocket};
+            c_int::try_from(tcp_stream.as_raw_socket()).map_err(|_| TASSLError::CustomError {
+                message: "The socket handle does not fit in a c_int".to_owned(),
+            })?
+        };
+        tassl.attach_socket(fd)?;
+        let timeout = Duration::from_secs(tassl.timeout_seconds as u64);
+        let handshake = Handshake {
+            stream: Some(TASSLStream { tassl, tcp_stream }),
+        };
+        match tokio::time::timeout(timeout, handshake).await {
+            Ok(result) => result,
+            Err(_) => Err(TASSLError::CustomError {
+                message: "Error Of SSL do handshake".to_owned(),
+            }),
+        }
+    }
+}
+
+struct Handshake {
+    stream: Option<TASSLStream>,
+}
+
+impl Future for Handshake {
+    type Output = Result<TASSLStream, TASSLError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.as_ref().unwrap();
+        match stream.poll_tassl_io(cx, |tassl| tassl.try_handshake()) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Ready(Ok(_)) => Poll::Ready(Ok(self.stream.take().unwrap())),
+        }
+    }
+}
+
+impl AsyncRead for TASSLStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let buffer = buf.initialize_unfilled();
+        match self.poll_tassl_io(cx, |tassl| tassl.try_read(buffer)) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(err)) => Poll::Ready(Err(to_io_error(err))),
+            Poll::Ready(Ok(size)) => {
+                buf.advance(size);
+                Poll::Ready(Ok(()))
+            }
+        }
+    }
+}
+
+impl AsyncWrite for TASSLStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.poll_tassl_io(cx, |tassl| tassl.try_write(buf))
+            .map_err(to_io_error)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.tassl.close();
+        Pin::new(&mut self.tcp_stream).poll_shutdown(cx)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{net::TcpListener, os::unix::io::AsRawFd, sync::mpsc, thread};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::tassl::ffi::*;
+    use crate::tassl::stream::*;
+
+    const CA_CERT: &[u8] = include_bytes!("../../../configs/testing/ca.crt");
+    const NODE_CERT: &[u8] = include_bytes!("../../../configs/testing/node.crt");
+    const NODE_KEY: &[u8] = include_bytes!("../../../configs/testing/node.key");
+    const SDK_CERT: &[u8] = include_bytes!("../../../configs/testing/sdk.crt");
+    const SDK_KEY: &[u8] = include_bytes!("../../../configs/testing/sdk.key");
+    const TOTAL_SIZE: usize = 32 * 1024 * 1024;
+
+    ///
+    /// 阻塞的 TLS 服务端：握手后等待通知才开始读取，读完 TOTAL_SIZE 字节后回复 pong。
+    fn run_server(listener: TcpListener, start_reading: mpsc::Receiver<()>) {
+        let (tcp_stream, _) = listener.accept().unwrap();
+        let server = TASSL::new(5);
+        server.init();
+        *server.ctx.borrow_mut() = Some(unsafe { SSL_CTX_new(TLSv1_2_server_method()) });
+        server
+            .load_auth_bytes(CA_CERT, NODE_KEY, NODE_CERT, b"", b"")
+            .unwrap();
+        unsafe {
+            let ssl = SSL_new(server.ctx.borrow().unwrap());
+            *server.ssl.borrow_mut() = Some(ssl);
+            assert!(SSL_set_fd(ssl, tcp_stream.as_raw_fd()) > 0);
+            assert!(SSL_accept(ssl) > 0);
+        }
+
+        start_reading.recv().unwrap();
+        let mut buffer = vec![0_u8; 64 * 1024];
+        let mut received = 0;
+        while received < TOTAL_SIZE {
+            received += server.read(&mut buffer).unwrap();
+        }
+        assert_eq!(TOTAL_SIZE, received);
+        server.write(b"pong").unwrap();
+        let _ = server.read(&mut buffer);
+    }
+
+    #[tokio::test]
+    async fn test_tassl_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as i32;
+        let (start_sender, start_receiver) = mpsc::channel();
+        let server = thread::spawn(move || run_server(listener, start_receiver));
+
+        let tassl = TASSL::new(5);
+        tassl.init();
+        tassl
+            .load_auth_bytes(CA_CERT, SDK_KEY, SDK_CERT, b"", b"")
+            .unwrap();
+        let mut stream = TASSLStream::connect(tassl, "127.0.0.1", port)
+            .await
+            .unwrap();
+
+        // 服务端尚未发送数据，读取需等待 socket 可读。
+        let mut buffer = [0_u8; 4];
+        assert_eq!(
+            TASSLIOStatus::WantRead,
+            stream.tassl.try_read(&mut buffer).unwrap()
+        );
+
+        // 服务端尚未读取数据，持续写入直到 socket 缓冲区写满。
+        let data = vec![7_u8; TOTAL_SIZE];
+        let mut written = 0;
+        loop {
+            match stream.tassl.try_write(&data[written..]).unwrap() {
+                TASSLIOStatus::Done(size) => written += size,
+                TASSLIOStatus::WantWrite => break,
+                TASSLIOStatus::WantRead => panic!("unexpected WantRead"),
+            }
+            assert!(written < TOTAL_SIZE, "the socket buffer was never filled");
+        }
+
+        // 余下的数据及回复分别由 WANT_WRITE 及 WANT_READ 驱动的 poll 完成。
+        start_sender.send(()).unwrap();
+        stream.write_all(&data[written..]).await.unwrap();
+        stream.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(b"pong", &buffer);
+        stream.shutdown().await.unwrap();
+        drop(stream);
+        server.join().unwrap();
+    }
+}
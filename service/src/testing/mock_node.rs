@@ -0,0 +1,658 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use ethereum_types::U256;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use keccak_hash::keccak;
+use serde_json::{json, Value as JSONValue};
+#[cfg(feature = "channel")]
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::oneshot;
+
+use crate::transaction::{decode_sign_transaction_data, SignedTransaction};
+#[cfg(feature = "channel")]
+use crate::{
+    channel::{
+        pack_channel_message_with_seq, read_channel_message, ChannelError, MessageType,
+        MAXIMUM_PROTOCOL_VERSION,
+    },
+    channel_pool::{ChannelConnection, ChannelPool},
+    config::Config,
+    web3::channel_fetcher::ChannelFetcher,
+};
+
+const INVALID_PARAMS_CODE: i32 = -32602;
+const METHOD_NOT_FOUND_CODE: i32 = -32601;
+const INVALID_REQUEST_CODE: i32 = -32600;
+
+// FISCO BCOS 默认的交易 block limit 上限（当前块高之后的 1000 个块）。
+const MAX_BLOCK_LIMIT_OFFSET: u64 = 1000;
+
+type CallHandler = Box<dyn Fn(&JSONValue) -> String + Send + Sync>;
+type TransactionHandler = Box<dyn Fn(&SignedTransaction) -> MockTransactionResult + Send + Sync>;
+
+///
+/// 交易的执行结果，`status` 及 `output` 会写入交易回执中。
+#[derive(Debug, Clone)]
+pub struct MockTransactionResult {
+    pub status: String,
+    pub output: String,
+}
+
+impl Default for MockTransactionResult {
+    fn default() -> MockTransactionResult {
+        MockTransactionResult {
+            status: String::from("0x0"),
+            output: String::from("0x"),
+        }
+    }
+}
+
+struct MockNodeState {
+    chain_id: u32,
+    group_id: u32,
+    sm_crypto: bool,
+    block_number: u64,
+    blocks: Vec<JSONValue>,
+    codes: HashMap<String, String>,
+    nonces: HashSet<U256>,
+    system_configs: HashMap<String, String>,
+    method_responses: HashMap<String, JSONValue>,
+    transactions: Vec<SignedTransaction>,
+    transaction_objects: HashMap<String, JSONValue>,
+    receipts: HashMap<String, JSONValue>,
+    method_calls: HashMap<String, usize>,
+    call_handler: CallHandler,
+    transaction_handler: TransactionHandler,
+    transaction_notify_enabled: bool,
+}
+
+fn to_hex_u64(value: u64) -> String {
+    format!("0x{:x}", value)
+}
+
+fn to_hex_bytes(value: &[u8]) -> String {
+    format!("0x{}", hex::encode(value))
+}
+
+fn create_block(number: u64, transaction_hashes: Vec<String>) -> JSONValue {
+    json!({
+        "number": to_hex_u64(number),
+        "hash": to_hex_bytes(keccak(number.to_be_bytes()).as_bytes()),
+        "parentHash": if number == 0 {
+            to_hex_bytes(&[0; 32])
+        } else {
+            to_hex_bytes(keccak((number - 1).to_be_bytes()).as_bytes())
+        },
+        "sealer": "0x0",
+        "sealerList": [],
+        "extraData": [],
+        "gasLimit": "0x0",
+        "gasUsed": "0x0",
+        "timestamp": "0x0",
+        "transactions": transaction_hashes,
+    })
+}
+
+fn invalid_params(message: &str) -> Result<JSONValue, (i32, String)> {
+    Err((INVALID_PARAMS_CODE, message.to_owned()))
+}
+
+impl MockNodeState {
+    fn new(chain_id: u32, group_id: u32, sm_crypto: bool) -> MockNodeState {
+        let mut system_configs = HashMap::new();
+        system_configs.insert(String::from("tx_count_limit"), String::from("1000"));
+        system_configs.insert(String::from("tx_gas_limit"), String::from("300000000"));
+        system_configs.insert(String::from("rpbft_epoch_sealer_num"), String::from("4"));
+        system_configs.insert(String::from("rpbft_epoch_block_num"), String::from("1000"));
+        system_configs.insert(String::from("consensus_timeout"), String::from("3"));
+        MockNodeState {
+            chain_id,
+            group_id,
+            sm_crypto,
+            block_number: 0,
+            blocks: vec![create_block(0, vec![])],
+            codes: HashMap::new(),
+            nonces: HashSet::new(),
+            system_configs,
+            method_responses: HashMap::new(),
+            transactions: vec![],
+            transaction_objects: HashMap::new(),
+            receipts: HashMap::new(),
+            method_calls: HashMap::new(),
+            call_handler: Box::new(|_| String::from("0x")),
+            transaction_handler: Box::new(|_| MockTransactionResult::default()),
+            transaction_notify_enabled: true,
+        }
+    }
+
+    fn get_block(&self, number: u64) -> JSONValue {
+        self.blocks
+            .get(number as usize)
+            .cloned()
+            .unwrap_or(JSONValue::Null)
+    }
+
+    fn send_raw_transaction(&mut self, params: &JSONValue) -> Result<JSONValue, (i32, String)> {
+        let data = match params[1].as_str() {
+            Some(data) => data.trim_start_matches("0x"),
+            None => return invalid_params("Missing transaction data"),
+        };
+        let data = hex::decode(data).map_err(|err| (INVALID_PARAMS_CODE, format!("{:?}", err)))?;
+        let transaction = decode_sign_transaction_data(&data, self.sm_crypto)
+            .map_err(|err| (INVALID_PARAMS_CODE, format!("{:?}", err)))?;
+        if transaction.chain_id != U256::from(self.chain_id) {
+            return invalid_params(&format!("Invalid chain id: {:?}", transaction.chain_id));
+        }
+        if transaction.group_id != U256::from(self.group_id) {
+            return invalid_params(&format!("Invalid group id: {:?}", transaction.group_id));
+        }
+        if transaction.block_limit <= U256::from(self.block_number)
+            || transaction.block_limit > U256::from(self.block_number + MAX_BLOCK_LIMIT_OFFSET)
+        {
+            return invalid_params(&format!(
+                "Invalid block limit: {:?}",
+                transaction.block_limit
+            ));
+        }
+        if !self.nonces.insert(transaction.nonce) {
+            return invalid_params("Transaction nonce already used");
+        }
+
+        let result = (self.transaction_handler)(&transaction);
+        let transaction_hash = to_hex_bytes(transaction.hash.as_bytes());
+        let from = to_hex_bytes(&transaction.sender);
+        let (to, contract_address) = if transaction.receive_address.is_empty() {
+            let mut seed = transaction.sender.clone();
+            seed.extend_from_slice(&<[u8; 32]>::from(transaction.nonce));
+            let contract_address = to_hex_bytes(&keccak(seed).as_bytes()[12..]);
+            self.codes
+                .insert(contract_address.clone(), to_hex_bytes(&transaction.data));
+            (JSONValue::Null, contract_address)
+        } else {
+            (
+                json!(to_hex_bytes(&transaction.receive_address)),
+                to_hex_bytes(&[0; 20]),
+            )
+        };
+        self.block_number += 1;
+        let block = create_block(self.block_number, vec![transaction_hash.clone()]);
+        self.transaction_objects.insert(
+            transaction_hash.clone(),
+            json!({
+                "blockHash": block["hash"],
+                "blockNumber": to_hex_u64(self.block_number),
+                "from": from,
+                "gas": format!("0x{:x}", transaction.gas),
+                "gasPrice": format!("0x{:x}", transaction.gas_price),
+                "hash": transaction_hash,
+                "input": to_hex_bytes(&transaction.data),
+                "nonce": format!("0x{:x}", transaction.nonce),
+                "to": to,
+                "transactionIndex": "0x0",
+                "value": "0x0",
+            }),
+        );
+        self.receipts.insert(
+            transaction_hash.clone(),
+            json!({
+                "blockHash": block["hash"],
+                "blockNumber": to_hex_u64(self.block_number),
+                "contractAddress": contract_address,
+                "from": from,
+                "gasUsed": "0x0",
+                "input": to_hex_bytes(&transaction.data),
+                "logs": [],
+                "logsBloom": to_hex_bytes(&[0; 256]),
+                "output": result.output,
+                "root": to_hex_bytes(&[0; 32]),
+                "status": result.status,
+                "to": to,
+                "transactionHash": transaction_hash,
+                "transactionIndex": "0x0",
+            }),
+        );
+        self.blocks.push(block);
+        self.transactions.push(transaction);
+        Ok(json!(transaction_hash))
+    }
+
+    fn handle_method(
+        &mut self,
+        method: &str,
+        params: &JSONValue,
+    ) -> Result<JSONValue, (i32, String)> {
+        if let Some(response) = self.method_responses.get(method) {
+            return Ok(response.clone());
+        }
+        match method {
+            "getClientVersion" => Ok(json!({
+                "Build Time": "20210201 10:00:00",
+                "Build Type": "Linux/clang/Release",
+                "Chain Id": self.chain_id.to_string(),
+                "FISCO-BCOS Version": "2.9.0",
+                "Git Branch": "HEAD",
+                "Git Commit Hash": "0000000000000000000000000000000000000000",
+                "Supported Version": "2.9.0",
+            })),
+            "getBlockNumber" => Ok(json!(to_hex_u64(self.block_number))),
+            "getPbftView" => Ok(json!(to_hex_u64(self.block_number))),
+            "getSealerList" | "getNodeIDList" | "getGroupPeers" => {
+                Ok(json!([hex::encode([1_u8; 64])]))
+            }
+            "getObserverList" => Ok(json!([])),
+            "getPeers" => Ok(json!([])),
+            "getGroupList" => Ok(json!([self.group_id.to_string()])),
+            "getConsensusStatus" | "getSyncStatus" => Ok(json!({
+                "blockNumber": self.block_number,
+                "nodeId": hex::encode([1_u8; 64]),
+            })),
+            "getPendingTransactions" => Ok(json!([])),
+            "getPendingTxSize" => Ok(json!("0x0")),
+            "getTotalTransactionCount" => Ok(json!({
+                "blockNumber": to_hex_u64(self.block_number),
+                "failedTxSum": "0x0",
+                "txSum": to_hex_u64(self.transactions.len() as u64),
+            })),
+            "getSystemConfigByKey" => Ok(json!(self
+                .system_configs
+                .get(params[1].as_str().unwrap_or(""))
+                .cloned()
+                .unwrap_or_default())),
+            "getCode" => Ok(json!(self
+                .codes
+                .get(&params[1].as_str().unwrap_or("").to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| String::from("0x")))),
+            "getBlockByNumber" | "getBlockHeaderByNumber" => {
+                let number = params[1].as_str().unwrap_or("");
+                match u64::from_str_radix(number.trim_start_matches("0x"), 16) {
+                    Ok(number) => Ok(self.get_block(number)),
+                    Err(_) => invalid_params(&format!("Invalid block number: {:?}", number)),
+                }
+            }
+            "getBlockHashByNumber" => {
+                let number = params[1].as_str().unwrap_or("");
+                match u64::from_str_radix(number.trim_start_matches("0x"), 16) {
+                    Ok(number) => Ok(self.get_block(number)["hash"].clone()),
+                    Err(_) => invalid_params(&format!("Invalid block number: {:?}", number)),
+                }
+            }
+            "getBlockByHash" | "getBlockHeaderByHash" => Ok(self
+                .blocks
+                .iter()
+                .find(|block| block["hash"].eq(&params[1]))
+                .cloned()
+                .unwrap_or(JSONValue::Null)),
+            "getTransactionByHash" => Ok(self
+                .transaction_objects
+                .get(params[1].as_str().unwrap_or(""))
+                .cloned()
+                .unwrap_or(JSONValue::Null)),
+            "getTransactionReceipt" => Ok(self
+                .receipts
+                .get(params[1].as_str().unwrap_or(""))
+                .cloned()
+                .unwrap_or(JSONValue::Null)),
+            "call" => Ok(json!({
+                "currentBlockNumber": to_hex_u64(self.block_number),
+                "output": (self.call_handler)(&params[1]),
+                "status": "0x0",
+            })),
+            "sendRawTransaction" | "sendRawTransactionAndGetProof" => {
+                self.send_raw_transaction(params)
+            }
+            _ => Err((METHOD_NOT_FOUND_CODE, String::from("Method not found"))),
+        }
+    }
+
+    fn handle_request(&mut self, request: &JSONValue) -> JSONValue {
+        let result = match request["method"].as_str() {
+            Some(method) => {
+                *self.method_calls.entry(method.to_owned()).or_insert(0) += 1;
+                self.handle_method(method, &request["params"])
+            }
+            None => Err((INVALID_REQUEST_CODE, String::from("Invalid Request"))),
+        };
+        match result {
+            Ok(result) => json!({
+                "id": request["id"],
+                "jsonrpc": "2.0",
+                "result": result,
+            }),
+            Err((code, message)) => json!({
+                "id": request["id"],
+                "jsonrpc": "2.0",
+                "error": {"code": code, "message": message},
+            }),
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<MockNodeState>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let body = hyper::body::to_bytes(request.into_body())
+        .await
+        .unwrap_or_default();
+    let response = match serde_json::from_slice::<JSONValue>(&body) {
+        Ok(JSONValue::Array(requests)) => {
+            let mut state = state.lock().unwrap();
+            JSONValue::Array(
+                requests
+                    .iter()
+                    .map(|request| state.handle_request(request))
+                    .collect(),
+            )
+        }
+        Ok(request) => state.lock().unwrap().handle_request(&request),
+        Err(_) => json!({
+            "id": null,
+            "jsonrpc": "2.0",
+            "error": {"code": -32700, "message": "Parse error"},
+        }),
+    };
+    Ok(Response::new(Body::from(response.to_string())))
+}
+
+///
+/// 以 channel 协议处理 stream 上的消息：响应握手、心跳及 RPC 请求，交易上链后与真实节点一样
+/// 以请求的 seq 推送 `TransactionNotify` 消息（内容为交易回执）。
+#[cfg(feature = "channel")]
+async fn serve_channel<S: AsyncRead + AsyncWrite + Unpin>(
+    state: Arc<Mutex<MockNodeState>>,
+    mut stream: S,
+) {
+    while let Ok(message) = read_channel_message(&mut stream).await {
+        let responses = if message.message_type == MessageType::ClientHandshake.value() {
+            vec![(
+                MessageType::ClientHandshake,
+                json!({"protocol": MAXIMUM_PROTOCOL_VERSION, "nodeVersion": "2.9.0"}),
+            )]
+        } else if message.message_type == MessageType::Heartbeat.value() {
+            vec![(MessageType::Heartbeat, json!({"heartBeat": 1}))]
+        } else if message.message_type == MessageType::RpcRequest.value() {
+            let request = serde_json::from_slice(&message.data).unwrap_or(JSONValue::Null);
+            let mut state = state.lock().unwrap();
+            let response = state.handle_request(&request);
+            let receipt = match request["method"].as_str() {
+                _ if !state.transaction_notify_enabled => None,
+                Some("sendRawTransaction") | Some("sendRawTransactionAndGetProof") => response
+                    ["result"]
+                    .as_str()
+                    .and_then(|transaction_hash| state.receipts.get(transaction_hash))
+                    .cloned(),
+                _ => None,
+            };
+            let mut responses = vec![(MessageType::RpcRequest, response)];
+            if let Some(receipt) = receipt {
+                responses.push((MessageType::TransactionNotify, receipt));
+            }
+            responses
+        } else {
+            vec![]
+        };
+        for (message_type, data) in responses {
+            let buffer = pack_channel_message_with_seq(
+                data.to_string().as_bytes(),
+                message_type,
+                &message.seq,
+            );
+            if stream.write_all(&buffer).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+///
+/// 进程内的模拟 FISCO BCOS JSON-RPC 节点，状态保存在内存中，用于在没有链的环境下进行集成测试。
+///
+/// 节点会解码并校验 `sendRawTransaction` 及 `sendRawTransactionAndGetProof` 收到的交易
+/// （签名、chain id、group id、block limit 及 nonce），每笔通过校验的交易会单独出一个块，
+/// 交易回执可立即通过 `getTransactionReceipt` 查询。
+///
+/// 启用 `channel` feature 时，可通过 `create_channel_fetcher` 以 channel 协议（内存连接，不经过 TLS）
+/// 访问节点，交易上链后节点会主动推送交易回执。
+///
+/// 节点在 drop 时停止。
+pub struct MockNode {
+    address: SocketAddr,
+    state: Arc<Mutex<MockNodeState>>,
+    shutdown_sender: Option<oneshot::Sender<()>>,
+}
+
+impl MockNode {
+    pub async fn start(
+        chain_id: u32,
+        group_id: u32,
+        sm_crypto: bool,
+    ) -> Result<MockNode, hyper::Error> {
+        let state = Arc::new(Mutex::new(MockNodeState::new(
+            chain_id, group_id, sm_crypto,
+        )));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let address = server.local_addr();
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            let _ = shutdown_receiver.await;
+        }));
+        Ok(MockNode {
+            address,
+            state,
+            shutdown_sender: Some(shutdown_sender),
+        })
+    }
+
+    pub fn host(&self) -> String {
+        self.address.ip().to_string()
+    }
+
+    pub fn port(&self) -> i32 {
+        self.address.port() as i32
+    }
+
+    pub fn get_block_number(&self) -> u64 {
+        self.state.lock().unwrap().block_number
+    }
+
+    ///
+    /// 返回节点收到的 method 请求的次数（包括 channel 连接上的请求）。
+    pub fn get_method_call_count(&self, method: &str) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .method_calls
+            .get(method)
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    ///
+    /// 以 channel 协议连接节点，返回使用该连接的 `ChannelFetcher`，config 中的节点地址及证书不会被使用。
+    #[cfg(feature = "channel")]
+    pub async fn create_channel_fetcher(
+        &self,
+        config: &Config,
+    ) -> Result<ChannelFetcher, ChannelError> {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(serve_channel(self.state.clone(), server));
+        let connection = ChannelConnection::create_with_stream(client, config, None).await?;
+        Ok(ChannelFetcher::with_pool(
+            config,
+            ChannelPool::with_connection(config, connection),
+        ))
+    }
+
+    ///
+    /// 返回所有通过校验的交易（按接收顺序）。
+    pub fn get_transactions(&self) -> Vec<SignedTransaction> {
+        self.state.lock().unwrap().transactions.clone()
+    }
+
+    ///
+    /// 固定某个方法的返回值（JSON-RPC 响应中的 `result`），优先于内置的处理逻辑。
+    pub fn set_method_response(&self, method: &str, result: JSONValue) {
+        self.state
+            .lock()
+            .unwrap()
+            .method_responses
+            .insert(method.to_owned(), result);
+    }
+
+    pub fn set_system_config(&self, key: &str, value: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .system_configs
+            .insert(key.to_owned(), value.to_owned());
+    }
+
+    pub fn set_code(&self, address: &str, code: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .codes
+            .insert(address.to_lowercase(), code.to_owned());
+    }
+
+    ///
+    /// 设置 `call` 的处理函数，参数为 `call` 请求中的交易对象（包含 `from`、`to`、`data` 等属性），
+    /// 返回值为 `output`（十六进制字符串）。
+    pub fn set_call_handler<F>(&self, handler: F)
+    where
+        F: Fn(&JSONValue) -> String + Send + Sync + 'static,
+    {
+        self.state.lock().unwrap().call_handler = Box::new(handler);
+    }
+
+    ///
+    /// 设置交易的处理函数，返回值决定交易回执中的 `status` 及 `output`。
+    pub fn set_transaction_handler<F>(&self, handler: F)
+    where
+        F: Fn(&SignedTransaction) -> MockTransactionResult + Send + Sync + 'static,
+    {
+        self.state.lock().unwrap().transaction_handler = Box::new(handler);
+    }
+
+    ///
+    /// 设置 channel 连接上交易上链后是否推送 `TransactionNotify` 消息（默认推送），用于模拟回执推送丢失。
+    pub fn set_transaction_notify_enabled(&self, enabled: bool) {
+        self.state.lock().unwrap().transaction_notify_enabled = enabled;
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::account::{create_account_from_private_key, Account};
+    use crate::testing::mock_node::*;
+    use crate::transaction::get_sign_transaction_data;
+    use crate::web3::fetcher_trait::FetcherTrait;
+    use crate::web3::rpc_fetcher::RPCFetcher;
+
+    fn create_account() -> Account {
+        let private_key =
+            hex::decode("a2b3c8a4ba4e0b12c5f9fe2d6f4f2c1b3c1e6a1d9d61c8d8b1b6b1a7ad1bbc2e")
+                .unwrap();
+        create_account_from_private_key(&private_key, false).unwrap()
+    }
+
+    fn generate_request_params(method: &str, params: &JSONValue) -> JSONValue {
+        json!({"id": 1, "jsonrpc": "2.0", "method": method, "params": params})
+    }
+
+    #[tokio::test]
+    async fn test_mock_node() {
+        let node = MockNode::start(1, 1, false).await.unwrap();
+        node.set_transaction_handler(|_| MockTransactionResult {
+            status: String::from("0x0"),
+            output: String::from("0x01"),
+        });
+        node.set_call_handler(|transaction| transaction["data"].as_str().unwrap().to_owned());
+        let fetcher = RPCFetcher::new(&node.host(), node.port()).unwrap();
+        let account = create_account();
+
+        let block_number = fetcher
+            .fetch(&generate_request_params("getBlockNumber", &json!([1])))
+            .await
+            .unwrap();
+        assert_eq!(json!("0x0"), block_number);
+
+        let to_address = "0x0000000000000000000000000000000000001000";
+        let transaction_data =
+            get_sign_transaction_data(&account, 1, 1, 500, to_address, &[1, 2], false).unwrap();
+        let params = json!([1, format!("0x{}", hex::encode(&transaction_data))]);
+        let transaction_hash = fetcher
+            .fetch(&generate_request_params("sendRawTransaction", &params))
+            .await
+            .unwrap();
+        assert!(fetcher
+            .fetch(&generate_request_params("sendRawTransaction", &params))
+            .await
+            .is_err());
+        let receipt = fetcher
+            .fetch(&generate_request_params(
+                "getTransactionReceipt",
+                &json!([1, transaction_hash]),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            json!(format!("0x{}", hex::encode(&account.address))),
+            receipt["from"]
+        );
+        assert_eq!(json!(to_address), receipt["to"]);
+        assert_eq!(json!("0x01"), receipt["output"]);
+        assert_eq!(1, node.get_block_number());
+        assert_eq!(vec![1, 2], node.get_transactions()[0].data);
+
+        let transaction_data =
+            get_sign_transaction_data(&account, 1, 2, 500, to_address, &[1, 2], false).unwrap();
+        let params = json!([1, format!("0x{}", hex::encode(&transaction_data))]);
+        assert!(fetcher
+            .fetch(&generate_request_params("sendRawTransaction", &params))
+            .await
+            .is_err());
+
+        let call_params =
+            json!([1, {"from": "0x0", "to": to_address, "value": "0x0", "data": "0x1234"}]);
+        let response = fetcher
+            .fetch(&generate_request_params("call", &call_params))
+            .await
+            .unwrap();
+        assert_eq!(json!("0x1234"), response["output"]);
+
+        node.set_system_config("tx_count_limit", "2000");
+        let response = fetcher
+            .fetch(&generate_request_params(
+                "getSystemConfigByKey",
+                &json!([1, "tx_count_limit"]),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(json!("2000"), response);
+    }
+}
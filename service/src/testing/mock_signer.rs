@@ -0,0 +1,144 @@
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value as JSONValue};
+use tokio::sync::oneshot;
+
+use crate::{account::Account, signer::sign_hash_with_private_key};
+
+struct MockSignerState {
+    account: Account,
+    sm_crypto: bool,
+    bearer_token: Option<String>,
+    sign_count: usize,
+}
+
+fn create_response(status: StatusCode, body: JSONValue) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response
+}
+
+async fn handle(
+    state: Arc<Mutex<MockSignerState>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let authorization = request
+        .headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let method = request.method().clone();
+    let body = hyper::body::to_bytes(request.into_body())
+        .await
+        .unwrap_or_default();
+
+    let mut state = state.lock().unwrap();
+    if let Some(bearer_token) = &state.bearer_token {
+        if authorization != Some(format!("Bearer {}", bearer_token)) {
+            return Ok(create_response(
+                StatusCode::UNAUTHORIZED,
+                json!({"error": "unauthorized"}),
+            ));
+        }
+    }
+    let response = match method {
+        Method::GET => create_response(
+            StatusCode::OK,
+            json!({ "public_key": format!("0x{}", hex::encode(&state.account.public_key)) }),
+        ),
+        Method::POST => {
+            let hash = serde_json::from_slice::<JSONValue>(&body)
+                .ok()
+                .and_then(|params| params["hash"].as_str().map(String::from))
+                .and_then(|hash| hex::decode(hash.trim_start_matches("0x")).ok());
+            let signature = hash.and_then(|hash| {
+                sign_hash_with_private_key(
+                    state.account.expose_private_key(),
+                    &hash,
+                    state.sm_crypto,
+                )
+                .ok()
+            });
+            match signature {
+                Some(signature) => {
+                    state.sign_count += 1;
+                    create_response(
+                        StatusCode::OK,
+                        json!({ "signature": format!("0x{}", hex::encode(&signature)) }),
+                    )
+                }
+                None => create_response(StatusCode::BAD_REQUEST, json!({"error": "invalid hash"})),
+            }
+        }
+        _ => create_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            json!({"error": "method not allowed"}),
+        ),
+    };
+    Ok(response)
+}
+
+///
+/// 本地的远程签名服务（接口参见 `RemoteSigner`），用于测试。
+pub struct MockSigner {
+    address: SocketAddr,
+    state: Arc<Mutex<MockSignerState>>,
+    shutdown_sender: Option<oneshot::Sender<()>>,
+}
+
+impl MockSigner {
+    pub async fn start(account: Account, sm_crypto: bool) -> Result<MockSigner, hyper::Error> {
+        let state = Arc::new(Mutex::new(MockSignerState {
+            account,
+            sm_crypto,
+            bearer_token: None,
+            sign_count: 0,
+        }));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let address = server.local_addr();
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            let _ = shutdown_receiver.await;
+        }));
+        Ok(MockSigner {
+            address,
+            state,
+            shutdown_sender: Some(shutdown_sender),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    ///
+    /// 设置后，请求需携带 `Authorization: Bearer <token>`。
+    pub fn set_bearer_token(&self, bearer_token: &str) {
+        self.state.lock().unwrap().bearer_token = Some(bearer_token.to_owned());
+    }
+
+    pub fn get_sign_count(&self) -> usize {
+        self.state.lock().unwrap().sign_count
+    }
+}
+
+impl Drop for MockSigner {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mock_node;
+pub mod mock_signer;
@@ -1,16 +1,19 @@
 use std::convert::TryInto;
 
 use ethereum_types::{H256, H512, U256};
-use rlp::RlpStream;
+use keccak_hash::keccak;
+use rlp::{Rlp, RlpStream};
 use thiserror::Error;
 use uuid::Uuid;
 use wedpr_l_crypto_hash_keccak256::WedprKeccak256;
-use wedpr_l_crypto_hash_sm3::WedprSm3;
 use wedpr_l_crypto_signature_secp256k1::WedprSecp256k1Recover;
-use wedpr_l_crypto_signature_sm2::WedprSm2p256v1;
-use wedpr_l_utils::traits::{Hash, Signature};
+use wedpr_l_utils::traits::Hash;
 
-use crate::account::Account;
+use crate::{
+    account::Account,
+    signer::{sign_hash_with_private_key, Signer, SignerError},
+    sm_crypto::{sm2_verify, sm3_hash},
+};
 
 #[derive(Error, Debug)]
 pub enum TransactionError {
@@ -19,10 +22,263 @@ pub enum TransactionError {
 
     #[error("std::array::TryFromSliceError")]
     TryFromSliceError(#[from] std::array::TryFromSliceError),
+
+    #[error("rlp::DecoderError")]
+    RlpDecoderError(#[from] rlp::DecoderError),
+
+    #[error("signer error")]
+    SignerError(#[from] SignerError),
+
+    #[error("transaction custom error")]
+    CustomError { message: String },
+}
+
+fn create_custom_error(message: String) -> TransactionError {
+    TransactionError::CustomError { message }
+}
+
+///
+/// 解码后的已签名交易，`sender` 为通过签名恢复（或验证）得到的交易发送方地址。
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas: U256,
+    pub block_limit: U256,
+    pub receive_address: Vec<u8>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub chain_id: U256,
+    pub group_id: U256,
+    pub extra_data: Vec<u8>,
+    pub hash: H256,
+    pub sender: Vec<u8>,
+}
+
+///
+/// 交易构造参数，默认值为：group id 及 chain id 为 1，gas 及 gas price 为 300000000，
+/// block limit 为当前块高加 500，nonce 为随机生成，extraData 为空。
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    group_id: u32,
+    chain_id: u32,
+    gas: U256,
+    gas_price: U256,
+    block_limit_offset: u32,
+    nonce: Option<U256>,
+    extra_data: Vec<u8>,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> TransactionBuilder {
+        TransactionBuilder {
+            group_id: 1,
+            chain_id: 1,
+            gas: U256::from(300000000),
+            gas_price: U256::from(300000000),
+            block_limit_offset: 500,
+            nonce: None,
+            extra_data: vec![],
+        }
+    }
+}
+
+impl TransactionBuilder {
+    pub fn new() -> TransactionBuilder {
+        TransactionBuilder::default()
+    }
+
+    pub fn group_id(mut self, group_id: u32) -> TransactionBuilder {
+        self.group_id = group_id;
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u32) -> TransactionBuilder {
+        self.chain_id = chain_id;
+        self
+    }
+
+    pub fn gas(mut self, gas: U256) -> TransactionBuilder {
+        self.gas = gas;
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: U256) -> TransactionBuilder {
+        self.gas_price = gas_price;
+        self
+    }
+
+    ///
+    /// block limit 为当前块高加 block_limit_offset，节点只接受 block limit 在（当前块高，当前块高 + 1000]
+    /// 范围内的交易。
+    pub fn block_limit_offset(mut self, block_limit_offset: u32) -> TransactionBuilder {
+        self.block_limit_offset = block_limit_offset;
+        self
+    }
+
+    ///
+    /// 指定 nonce，节点会拒绝 nonce 重复的交易，因此同一个 `TransactionBuilder` 不应用于发送多笔交易。
+    pub fn nonce(mut self, nonce: U256) -> TransactionBuilder {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn extra_data(mut self, extra_data: &[u8]) -> TransactionBuilder {
+        self.extra_data = extra_data.to_vec();
+        self
+    }
+
+    pub fn get_block_limit(&self, block_number: u32) -> u32 {
+        block_number.saturating_add(self.block_limit_offset)
+    }
+
+    ///
+    /// 构造并由 account 签名交易，返回 rlp 编码后的交易数据。
+    pub fn sign(
+        &self,
+        account: &Account,
+        block_limit: u32,
+        to_address: &str,
+        data: &[u8],
+        sm_crypto: bool,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let transaction = TransactionFields::new(self, block_limit, to_address, data)?;
+        let tx_hash = transaction.hash(sm_crypto)?;
+        let signature = sign_hash_with_private_key(
+            account.expose_private_key(),
+            tx_hash.as_bytes(),
+            sm_crypto,
+        )?;
+        transaction.encode_with_signature(&signature, &account.public_key, sm_crypto)
+    }
+
+    ///
+    /// 与 `sign` 相同，但由 signer 签名（参见 `Signer`）。
+    pub async fn sign_with_signer(
+        &self,
+        signer: &(dyn Signer + Send + Sync),
+        block_limit: u32,
+        to_address: &str,
+        data: &[u8],
+        sm_crypto: bool,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let transaction = TransactionFields::new(self, block_limit, to_address, data)?;
+        let tx_hash = transaction.hash(sm_crypto)?;
+        let signature = signer.sign_hash(tx_hash.as_bytes(), sm_crypto).await?;
+        transaction.encode_with_signature(&signature, &signer.public_key(), sm_crypto)
+    }
+}
+
+struct TransactionFields {
+    nonce: U256,
+    gas_price: U256,
+    gas: U256,
+    block_limit: U256,
+    receive_address: Vec<u8>,
+    value: U256,
+    data: Vec<u8>,
+    chain_id: U256,
+    group_id: U256,
+    extra_data: Vec<u8>,
 }
 
 // 编码规则详见：
 // https://fisco-bcos-documentation.readthedocs.io/zh_CN/latest/docs/design/protocol_description.html#rlp
+impl TransactionFields {
+    fn new(
+        builder: &TransactionBuilder,
+        block_limit: u32,
+        to_address: &str,
+        data: &[u8],
+    ) -> Result<TransactionFields, TransactionError> {
+        let receive_address = if to_address.is_empty() {
+            Vec::from("")
+        } else {
+            hex::decode(to_address.to_owned().trim_start_matches("0x").as_bytes())?
+        };
+        Ok(TransactionFields {
+            nonce: builder.nonce.unwrap_or_else(|| {
+                U256::from(Uuid::new_v4().to_string().replace("-", "").as_bytes())
+            }),
+            gas_price: builder.gas_price,
+            gas: builder.gas,
+            block_limit: U256::from(block_limit),
+            receive_address,
+            value: U256::from(0),
+            data: data.to_vec(),
+            chain_id: U256::from(builder.chain_id),
+            group_id: U256::from(builder.group_id),
+            extra_data: builder.extra_data.clone(),
+        })
+    }
+
+    fn append_to(&self, stream: &mut RlpStream) {
+        stream.append(&self.nonce);
+        stream.append(&self.gas_price);
+        stream.append(&self.gas);
+        stream.append(&self.block_limit);
+        stream.append(&self.receive_address);
+        stream.append(&self.value);
+        stream.append(&self.data);
+        stream.append(&self.chain_id);
+        stream.append(&self.group_id);
+        stream.append(&self.extra_data);
+    }
+
+    fn hash(&self, sm_crypto: bool) -> Result<H256, TransactionError> {
+        let mut stream = RlpStream::new();
+        stream.begin_list(10);
+        self.append_to(&mut stream);
+        let transaction_encode_data = stream.out().to_vec();
+        let msg_hash = if sm_crypto {
+            sm3_hash(&transaction_encode_data).map_err(create_custom_error)?
+        } else {
+            let keccak256 = WedprKeccak256::default();
+            keccak256.hash(&transaction_encode_data)
+        };
+        Ok(H256::from_slice(&msg_hash))
+    }
+
+    fn encode_with_signature(
+        &self,
+        signature: &[u8],
+        public_key: &[u8],
+        sm_crypto: bool,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let expected_len = if sm_crypto { 64 } else { 65 };
+        if signature.len() < expected_len || (sm_crypto && public_key.len() < 64) {
+            return Err(TransactionError::CustomError {
+                message: format!("Invalid signature length: {:?}", signature.len()),
+            });
+        }
+        let r = &signature[0..32];
+        let s = &signature[32..64];
+        let v = if sm_crypto {
+            public_key[0..64].to_vec()
+        } else {
+            let val = (&signature[64..])[0] as u64;
+            if val == 4 {
+                4_u64.to_be_bytes().to_vec()
+            } else {
+                (val + 27).to_be_bytes().to_vec()
+            }
+        };
+        let mut stream = RlpStream::new();
+        stream.begin_list(13);
+        self.append_to(&mut stream);
+        if v.len() == 8 {
+            stream.append(&u64::from_be_bytes(v[0..8].try_into().unwrap()));
+        } else {
+            stream.append(&H512::from_slice(&v));
+        }
+        stream.append(&H256::from_slice(r));
+        stream.append(&H256::from_slice(s));
+        Ok(stream.out().to_vec())
+    }
+}
+
+///
+/// 使用默认参数（参见 `TransactionBuilder`）构造并签名交易。
 pub fn get_sign_transaction_data(
     account: &Account,
     group_id: u32,
@@ -32,82 +288,138 @@ pub fn get_sign_transaction_data(
     data: &[u8],
     sm_crypto: bool,
 ) -> Result<Vec<u8>, TransactionError> {
-    let nonce = U256::from(Uuid::new_v4().to_string().replace("-", "").as_bytes());
-    let gas_price = U256::from(300000000);
-    let gas = U256::from(300000000);
-    let block_limit = U256::from(block_limit);
-    let receive_address = if to_address.len() == 0 {
-        Vec::from("")
-    } else {
-        hex::decode(to_address.to_owned().trim_start_matches("0x").as_bytes())?
-    };
-    let value = U256::from(0);
-    let chain_id = U256::from(chain_id);
-    let group_id = U256::from(group_id);
-    let extra_data = b"".to_vec();
+    TransactionBuilder::new()
+        .group_id(group_id)
+        .chain_id(chain_id)
+        .sign(account, block_limit, to_address, data, sm_crypto)
+}
+
+///
+/// 与 `get_sign_transaction_data` 相同，但由 signer 签名（参见 `Signer`）。
+pub async fn get_sign_transaction_data_with_signer(
+    signer: &(dyn Signer + Send + Sync),
+    group_id: u32,
+    chain_id: u32,
+    block_limit: u32,
+    to_address: &str,
+    data: &[u8],
+    sm_crypto: bool,
+) -> Result<Vec<u8>, TransactionError> {
+    TransactionBuilder::new()
+        .group_id(group_id)
+        .chain_id(chain_id)
+        .sign_with_signer(signer, block_limit, to_address, data, sm_crypto)
+        .await
+}
+
+///
+/// 解码 `get_sign_transaction_data` 生成的交易数据，并校验签名。
+pub fn decode_sign_transaction_data(
+    data: &[u8],
+    sm_crypto: bool,
+) -> Result<SignedTransaction, TransactionError> {
+    let rlp = Rlp::new(data);
+    if rlp.item_count()? != 13 {
+        return Err(TransactionError::CustomError {
+            message: format!("Invalid transaction item count: {:?}", rlp.item_count()?),
+        });
+    }
     let mut stream = RlpStream::new();
     stream.begin_list(10);
-    stream.append(&nonce);
-    stream.append(&gas_price);
-    stream.append(&gas);
-    stream.append(&block_limit);
-    stream.append(&receive_address);
-    stream.append(&value);
-    stream.append(&data);
-    stream.append(&chain_id);
-    stream.append(&group_id);
-    stream.append(&extra_data);
+    for index in 0..10 {
+        stream.append_raw(rlp.at(index)?.as_raw(), 1);
+    }
     let transaction_encode_data = stream.out().to_vec();
-    let msg_hash = if sm_crypto {
-        let sm3_hash = WedprSm3::default();
-        sm3_hash.hash(&transaction_encode_data)
+    let r: H256 = rlp.val_at(11)?;
+    let s: H256 = rlp.val_at(12)?;
+    let sender = if sm_crypto {
+        let tx_hash = sm3_hash(&transaction_encode_data).map_err(create_custom_error)?;
+        let public_key: H512 = rlp.val_at(10)?;
+        let mut signature = r.as_bytes().to_vec();
+        signature.extend_from_slice(s.as_bytes());
+        let mut full_public_key = vec![4_u8];
+        full_public_key.extend_from_slice(public_key.as_bytes());
+        if !sm2_verify(&full_public_key, &tx_hash, &signature) {
+            return Err(TransactionError::CustomError {
+                message: "Invalid transaction signature".to_owned(),
+            });
+        }
+        sm3_hash(public_key.as_bytes()).map_err(create_custom_error)?[12..].to_vec()
     } else {
-        let keccak256 = WedprKeccak256::default();
-        keccak256.hash(&transaction_encode_data)
+        let tx_hash = WedprKeccak256::default().hash(&transaction_encode_data);
+        let v: u64 = rlp.val_at(10)?;
+        if v != 27 && v != 28 {
+            return Err(TransactionError::CustomError {
+                message: format!("Invalid transaction signature v: {:?}", v),
+            });
+        }
+        let mut signature = r.as_bytes().to_vec();
+        signature.extend_from_slice(s.as_bytes());
+        signature.push((v - 27) as u8);
+        let public_key = WedprSecp256k1Recover::default()
+            .recover_public_key(&tx_hash, &signature)
+            .map_err(|_| TransactionError::CustomError {
+                message: "Invalid transaction signature".to_owned(),
+            })?;
+        let public_key = if public_key.len() == 65 {
+            &public_key[1..]
+        } else {
+            &public_key[..]
+        };
+        keccak(public_key).as_bytes()[12..].to_vec()
     };
-    let tx_hash = H256::from_slice(&msg_hash);
-    let signature = if sm_crypto {
-        let signer = WedprSm2p256v1::default();
-        signer
-            .sign(&account.private_key, &tx_hash.as_bytes().to_vec())
-            .unwrap()
+    let hash = if sm_crypto {
+        H256::from_slice(&sm3_hash(data).map_err(create_custom_error)?)
     } else {
-        let signer = WedprSecp256k1Recover::default();
-        signer
-            .sign(&account.private_key, &tx_hash.as_bytes().to_vec())
-            .unwrap()
+        H256::from_slice(keccak(data).as_bytes())
     };
+    Ok(SignedTransaction {
+        nonce: rlp.val_at(0)?,
+        gas_price: rlp.val_at(1)?,
+        gas: rlp.val_at(2)?,
+        block_limit: rlp.val_at(3)?,
+        receive_address: rlp.val_at(4)?,
+        value: rlp.val_at(5)?,
+        data: rlp.val_at(6)?,
+        chain_id: rlp.val_at(7)?,
+        group_id: rlp.val_at(8)?,
+        extra_data: rlp.val_at(9)?,
+        hash,
+        sender,
+    })
+}
 
-    let r = &signature[0..32];
-    let s = &signature[32..64];
-    let v = if sm_crypto {
-        account.public_key.clone()[0..64].to_vec()
-    } else {
-        let val = (&signature[64..])[0] as u64;
-        if val == 4 {
-            4_u64.to_be_bytes().to_vec()
-        } else {
-            (val + 27).to_be_bytes().to_vec()
-        }
-    };
-    let mut stream = RlpStream::new();
-    stream.begin_list(13);
-    stream.append(&nonce);
-    stream.append(&gas_price);
-    stream.append(&gas);
-    stream.append(&block_limit);
-    stream.append(&receive_address);
-    stream.append(&value);
-    stream.append(&data);
-    stream.append(&chain_id);
-    stream.append(&group_id);
-    stream.append(&extra_data);
-    if v.len() == 8 {
-        stream.append(&u64::from_be_bytes(v[0..8].try_into().unwrap()));
-    } else {
-        stream.append(&H512::from_slice(&v));
+#[cfg(test)]
+mod tests {
+    use crate::account::generate_account;
+    use crate::transaction::*;
+
+    #[test]
+    fn test_transaction_builder() {
+        let account = generate_account(false).unwrap();
+        let builder = TransactionBuilder::new()
+            .group_id(2)
+            .gas(U256::from(1_000_000_000))
+            .gas_price(U256::from(1))
+            .block_limit_offset(100)
+            .nonce(U256::from(42))
+            .extra_data(b"order:1");
+        let block_limit = builder.get_block_limit(10);
+        let data = builder
+            .sign(&account, block_limit, "", &[1, 2, 3], false)
+            .unwrap();
+        let transaction = decode_sign_transaction_data(&data, false).unwrap();
+        assert_eq!(U256::from(2), transaction.group_id);
+        assert_eq!(U256::from(1_000_000_000), transaction.gas);
+        assert_eq!(U256::from(1), transaction.gas_price);
+        assert_eq!(U256::from(110), transaction.block_limit);
+        assert_eq!(U256::from(42), transaction.nonce);
+        assert_eq!(b"order:1".to_vec(), transaction.extra_data);
+        assert_eq!(account.address, transaction.sender);
+
+        let data = get_sign_transaction_data(&account, 1, 1, 510, "", &[1, 2, 3], false).unwrap();
+        let transaction = decode_sign_transaction_data(&data, false).unwrap();
+        assert_eq!(U256::from(300000000), transaction.gas);
+        assert!(transaction.extra_data.is_empty());
     }
-    stream.append(&H256::from_slice(r));
-    stream.append(&H256::from_slice(s));
-    Ok(stream.out().to_vec())
 }
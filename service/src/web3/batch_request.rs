@@ -0,0 +1,93 @@
+use serde_json::{json, Value as JSONValue};
+
+use crate::web3::{
+    fetcher_trait::FetcherTrait,
+    service::{generate_request_params, ServiceError},
+};
+
+///
+/// 批量请求，通过 `Service::batch` 创建。
+/// `rpc` 模式下所有请求会作为一个 JSON-RPC 数组发出，`channel` 模式下请求会在长连接上并发发出。
+pub struct BatchRequest<'a> {
+    fetcher: &'a (dyn FetcherTrait + Send + Sync),
+    group_id: u32,
+    requests: Vec<JSONValue>,
+}
+
+impl<'a> BatchRequest<'a> {
+    pub fn new(fetcher: &'a (dyn FetcherTrait + Send + Sync), group_id: u32) -> BatchRequest<'a> {
+        BatchRequest {
+            fetcher,
+            group_id,
+            requests: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    ///
+    /// 加入任意方法的请求，params 为该方法完整的参数列表（包含 group id）。
+    pub fn add_request(&mut self, method: &str, params: &JSONValue) -> &mut BatchRequest<'a> {
+        self.requests.push(generate_request_params(method, params));
+        self
+    }
+
+    pub fn get_block_by_hash(
+        &mut self,
+        block_hash: &str,
+        include_transactions: bool,
+    ) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, block_hash, include_transactions]);
+        self.add_request("getBlockByHash", &params)
+    }
+
+    pub fn get_block_by_number(
+        &mut self,
+        block_number: &str,
+        include_transactions: bool,
+    ) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, block_number, include_transactions]);
+        self.add_request("getBlockByNumber", &params)
+    }
+
+    pub fn get_block_header_by_number(
+        &mut self,
+        block_number: &str,
+        include_transactions: bool,
+    ) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, block_number, include_transactions]);
+        self.add_request("getBlockHeaderByNumber", &params)
+    }
+
+    pub fn get_block_hash_by_number(&mut self, block_number: &str) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, block_number]);
+        self.add_request("getBlockHashByNumber", &params)
+    }
+
+    pub fn get_transaction_by_hash(&mut self, transaction_hash: &str) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, transaction_hash]);
+        self.add_request("getTransactionByHash", &params)
+    }
+
+    pub fn get_transaction_receipt(&mut self, transaction_hash: &str) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, transaction_hash]);
+        self.add_request("getTransactionReceipt", &params)
+    }
+
+    pub fn get_code(&mut self, address: &str) -> &mut BatchRequest<'a> {
+        let params = json!([self.group_id, address]);
+        self.add_request("getCode", &params)
+    }
+
+    ///
+    /// 发送所有请求，返回结果的顺序与加入请求的顺序一致，单个请求的失败不影响其他请求。
+    pub async fn send(&self) -> Result<Vec<Result<JSONValue, ServiceError>>, ServiceError> {
+        self.fetcher.fetch_batch(&self.requests).await
+    }
+}
@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use serde_json::Value as JSONValue;
 
 use crate::{
-    channel::{channel_read, open_tassl, pack_channel_message, MessageType},
+    channel::{ChannelError, MessageType},
+    channel_pool::ChannelPool,
     config::Config,
     web3::{
         fetcher_trait::{parse_response, FetcherTrait},
@@ -11,13 +14,23 @@ use crate::{
 };
 
 pub struct ChannelFetcher {
-    config: Config,
+    pool: ChannelPool,
+    timeout_seconds: i64,
 }
 
 impl ChannelFetcher {
     pub fn new(config: &Config) -> ChannelFetcher {
         ChannelFetcher {
-            config: config.clone(),
+            pool: ChannelPool::new(config),
+            timeout_seconds: config.timeout_seconds,
+        }
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn with_pool(config: &Config, pool: ChannelPool) -> ChannelFetcher {
+        ChannelFetcher {
+            pool,
+            timeout_seconds: config.timeout_seconds,
         }
     }
 }
@@ -25,12 +38,33 @@ impl ChannelFetcher {
 #[async_trait]
 impl FetcherTrait for ChannelFetcher {
     async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
-        let tassl = open_tassl(&self.config)?;
-        let request_data =
-            pack_channel_message(&serde_json::to_vec(&params)?, MessageType::RpcRequest);
-        tassl.write(&request_data)?;
-        let response = channel_read(&tassl)?;
-        tassl.close();
+        let request_data = serde_json::to_vec(&params)?;
+        let response = self
+            .pool
+            .request(&request_data, MessageType::RpcRequest)
+            .await?;
         parse_response(&response)
     }
+
+    ///
+    /// 节点在交易上链后会以请求的 seq 推送 `TransactionNotify` 消息，消息内容即为交易回执。
+    /// 交易请求成功后，等待推送超时或连接断开时交易仍可能上链，此时回执为 null，由调用方查询回执。
+    async fn fetch_with_receipt(
+        &self,
+        params: &JSONValue,
+    ) -> Result<Option<(JSONValue, JSONValue)>, ServiceError> {
+        let request_data = serde_json::to_vec(&params)?;
+        let (response, notify_receiver) = self
+            .pool
+            .request_with_notify(&request_data, MessageType::RpcRequest)
+            .await?;
+        let result = parse_response(&response)?;
+        let timeout = Duration::from_secs(self.timeout_seconds as u64);
+        let receipt = match notify_receiver.receive(timeout).await {
+            Ok(receipt) => receipt.unwrap_or(JSONValue::Null),
+            Err(ChannelError::DeadConnection { .. }) => JSONValue::Null,
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some((result, receipt)))
+    }
 }
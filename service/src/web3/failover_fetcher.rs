@@ -0,0 +1,371 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde_json::{json, Value as JSONValue};
+
+use crate::{
+    config::{Config, Node},
+    web3::{
+        fetcher_trait::FetcherTrait,
+        service::{create_node_fetcher, ServiceError},
+    },
+};
+
+struct NodeState {
+    node: Node,
+    fetcher: Box<dyn FetcherTrait + Send + Sync>,
+    healthy: AtomicBool,
+    probing: AtomicBool,
+}
+
+///
+/// 只有网络层面的错误（IO、TLS、channel、http）才认为节点不可用，
+/// 节点返回的业务错误（如 FiscoBcosError）不会触发切换。
+fn is_node_error(err: &ServiceError) -> bool {
+    match err {
+        ServiceError::StdIOError(_) | ServiceError::HyperError(_) => true,
+        #[cfg(feature = "channel")]
+        ServiceError::ChannelError(_) | ServiceError::TASSLError(_) => true,
+        _ => false,
+    }
+}
+
+///
+/// 只读的请求可在其他节点上安全重试，发送交易等请求失败后不会重试，以免重复上链。
+fn is_idempotent_method(method: &str) -> bool {
+    method.starts_with("get") || method.eq("call")
+}
+
+async fn probe_node(state: Weak<NodeState>, interval: Duration) {
+    let params = json!({
+        "id": 1,
+        "jsonrpc": "2.0",
+        "method": "getClientVersion",
+        "params": [],
+    });
+    loop {
+        tokio::time::sleep(interval).await;
+        let state = match state.upgrade() {
+            Some(state) => state,
+            None => return,
+        };
+        if state.fetcher.fetch(&params).await.is_ok() {
+            state.healthy.store(true, Ordering::SeqCst);
+            state.probing.store(false, Ordering::SeqCst);
+            return;
+        }
+    }
+}
+
+///
+/// 多节点的 fetcher，按 `round_robin` 或 `priority` 策略选择节点。
+/// 节点出现网络错误后会被标记为不可用，并在后台定时探测，恢复后重新参与选择。
+pub struct FailoverFetcher {
+    nodes: Vec<Arc<NodeState>>,
+    strategy: String,
+    probe_interval: Duration,
+    cursor: AtomicUsize,
+}
+
+impl FailoverFetcher {
+    fn get_candidate_indexes(&self) -> Vec<usize> {
+        let size = self.nodes.len();
+        let mut indexes: Vec<usize> = if self.strategy.eq("priority") {
+            let mut indexes: Vec<usize> = (0..size).collect();
+            indexes.sort_by_key(|index| self.nodes[*index].node.priority);
+            indexes
+        } else {
+            let start = self.cursor.fetch_add(1, Ordering::Relaxed);
+            (0..size).map(|offset| (start + offset) % size).collect()
+        };
+        // 不可用的节点排在最后，所有节点均不可用时仍会逐个尝试。
+        indexes.sort_by_key(|index| !self.nodes[*index].healthy.load(Ordering::SeqCst));
+        indexes
+    }
+
+    fn mark_unhealthy(&self, index: usize) {
+        let state = &self.nodes[index];
+        state.healthy.store(false, Ordering::SeqCst);
+        if !state.probing.swap(true, Ordering::SeqCst) {
+            tokio::spawn(probe_node(Arc::downgrade(state), self.probe_interval));
+        }
+    }
+
+    pub fn new(config: &Config) -> Result<FailoverFetcher, ServiceError> {
+        let fetchers = config
+            .get_nodes()
+            .into_iter()
+            .map(|node| Ok((node.clone(), create_node_fetcher(&config.with_node(&node))?)))
+            .collect::<Result<Vec<_>, ServiceError>>()?;
+        FailoverFetcher::new_with_fetchers(
+            fetchers,
+            &config.load_balance_strategy,
+            config.node_probe_interval_seconds,
+        )
+    }
+
+    pub fn new_with_fetchers(
+        fetchers: Vec<(Node, Box<dyn FetcherTrait + Send + Sync>)>,
+        strategy: &str,
+        probe_interval_seconds: u64,
+    ) -> Result<FailoverFetcher, ServiceError> {
+        if fetchers.is_empty() {
+            return Err(ServiceError::CustomError {
+                message: "At least one node is required".to_owned(),
+            });
+        }
+        if !strategy.eq("round_robin") && !strategy.eq("priority") {
+            return Err(ServiceError::CustomError {
+                message: format!("Invalid load balance strategy: {:?}", strategy),
+            });
+        }
+        Ok(FailoverFetcher {
+            nodes: fetchers
+                .into_iter()
+                .map(|(node, fetcher)| {
+                    Arc::new(NodeState {
+                        node,
+                        fetcher,
+                        healthy: AtomicBool::new(true),
+                        probing: AtomicBool::new(false),
+                    })
+                })
+                .collect(),
+            strategy: strategy.to_owned(),
+            probe_interval: Duration::from_secs(probe_interval_seconds),
+            cursor: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn is_node_healthy(&self, host: &str, port: i32) -> Option<bool> {
+        self.nodes
+            .iter()
+            .find(|state| state.node.host.eq(host) && state.node.port == port)
+            .map(|state| state.healthy.load(Ordering::SeqCst))
+    }
+}
+
+#[async_trait]
+impl FetcherTrait for FailoverFetcher {
+    async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+        let retryable = is_idempotent_method(params["method"].as_str().unwrap_or(""));
+        let mut last_error = None;
+        for index in self.get_candidate_indexes() {
+            match self.nodes[index].fetcher.fetch(params).await {
+                Err(err) if is_node_error(&err) => {
+                    self.mark_unhealthy(index);
+                    if !retryable {
+                        return Err(err);
+                    }
+                    last_error = Some(err);
+                }
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap())
+    }
+
+    ///
+    /// 整批请求发送到同一节点，出现网络错误时标记该节点不可用；
+    /// 仅当批量请求均为只读请求时才会在其他节点上重试整批请求。
+    async fn fetch_batch(
+        &self,
+        params_list: &[JSONValue],
+    ) -> Result<Vec<Result<JSONValue, ServiceError>>, ServiceError> {
+        let retryable = params_list
+            .iter()
+            .all(|params| is_idempotent_method(params["method"].as_str().unwrap_or("")));
+        let mut last_result = None;
+        for index in self.get_candidate_indexes() {
+            let result = self.nodes[index].fetcher.fetch_batch(params_list).await;
+            let failed = match &result {
+                Err(err) => is_node_error(err),
+                Ok(results) => results
+                    .iter()
+                    .any(|result| matches!(result, Err(err) if is_node_error(err))),
+            };
+            if !failed {
+                return result;
+            }
+            self.mark_unhealthy(index);
+            if !retryable {
+                return result;
+            }
+            last_result = Some(result);
+        }
+        last_result.unwrap()
+    }
+
+    ///
+    /// 发送交易不会在其他节点上重试，仅使用当前选中的节点。
+    async fn fetch_with_receipt(
+        &self,
+        params: &JSONValue,
+    ) -> Result<Option<(JSONValue, JSONValue)>, ServiceError> {
+        let index = self.get_candidate_indexes()[0];
+        let result = self.nodes[index].fetcher.fetch_with_receipt(params).await;
+        if let Err(err) = &result {
+            if is_node_error(err) {
+                self.mark_unhealthy(index);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use serde_json::{json, Value as JSONValue};
+
+    use crate::config::Node;
+    use crate::web3::failover_fetcher::*;
+
+    struct MockFetcher {
+        available: Arc<AtomicBool>,
+        count: Arc<AtomicUsize>,
+        result: JSONValue,
+    }
+
+    #[async_trait]
+    impl FetcherTrait for MockFetcher {
+        async fn fetch(&self, _params: &JSONValue) -> Result<JSONValue, ServiceError> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if self.available.load(Ordering::SeqCst) {
+                Ok(self.result.clone())
+            } else {
+                Err(ServiceError::StdIOError(std::io::Error::from(
+                    std::io::ErrorKind::ConnectionRefused,
+                )))
+            }
+        }
+    }
+
+    fn create_node(host: &str, priority: i32) -> Node {
+        Node {
+            host: host.to_owned(),
+            port: 20200,
+            priority,
+        }
+    }
+
+    fn create_mock_fetcher(
+        available: &Arc<AtomicBool>,
+        count: &Arc<AtomicUsize>,
+        result: JSONValue,
+    ) -> Box<dyn FetcherTrait + Send + Sync> {
+        Box::new(MockFetcher {
+            available: available.clone(),
+            count: count.clone(),
+            result,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_failover_fetcher() {
+        let first_available = Arc::new(AtomicBool::new(false));
+        let first_count = Arc::new(AtomicUsize::new(0));
+        let second_available = Arc::new(AtomicBool::new(true));
+        let second_count = Arc::new(AtomicUsize::new(0));
+        let fetcher = FailoverFetcher::new_with_fetchers(
+            vec![
+                (
+                    create_node("node0", 0),
+                    create_mock_fetcher(&first_available, &first_count, json!("node0")),
+                ),
+                (
+                    create_node("node1", 1),
+                    create_mock_fetcher(&second_available, &second_count, json!("node1")),
+                ),
+            ],
+            "priority",
+            1,
+        )
+        .unwrap();
+
+        let read_params = json!({"method": "getBlockNumber", "params": [1]});
+        assert_eq!(json!("node1"), fetcher.fetch(&read_params).await.unwrap());
+        assert_eq!(Some(false), fetcher.is_node_healthy("node0", 20200));
+        assert_eq!(json!("node1"), fetcher.fetch(&read_params).await.unwrap());
+        assert_eq!(1, first_count.load(Ordering::SeqCst));
+
+        first_available.store(true, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+        assert_eq!(Some(true), fetcher.is_node_healthy("node0", 20200));
+        assert_eq!(json!("node0"), fetcher.fetch(&read_params).await.unwrap());
+
+        first_available.store(false, Ordering::SeqCst);
+        let write_params = json!({"method": "sendRawTransaction", "params": [1, "0x00"]});
+        assert!(fetcher.fetch(&write_params).await.is_err());
+        assert_eq!(2, second_count.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_batch() {
+        let create_fetcher = |first_count: &Arc<AtomicUsize>, second_count: &Arc<AtomicUsize>| {
+            FailoverFetcher::new_with_fetchers(
+                vec![
+                    (
+                        create_node("node0", 0),
+                        create_mock_fetcher(
+                            &Arc::new(AtomicBool::new(false)),
+                            first_count,
+                            json!("node0"),
+                        ),
+                    ),
+                    (
+                        create_node("node1", 1),
+                        create_mock_fetcher(
+                            &Arc::new(AtomicBool::new(true)),
+                            second_count,
+                            json!("node1"),
+                        ),
+                    ),
+                ],
+                "priority",
+                60,
+            )
+            .unwrap()
+        };
+        let read_params = json!({"method": "getBlockNumber", "params": [1]});
+        let write_params = json!({"method": "sendRawTransaction", "params": [1, "0x00"]});
+
+        let first_count = Arc::new(AtomicUsize::new(0));
+        let second_count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_fetcher(&first_count, &second_count);
+        let results = fetcher
+            .fetch_batch(&[read_params.clone(), read_params.clone()])
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![json!("node1"), json!("node1")],
+            results
+                .into_iter()
+                .map(|result| result.unwrap())
+                .collect::<Vec<JSONValue>>()
+        );
+        assert_eq!(Some(false), fetcher.is_node_healthy("node0", 20200));
+        assert_eq!(2, first_count.load(Ordering::SeqCst));
+        assert_eq!(2, second_count.load(Ordering::SeqCst));
+
+        let first_count = Arc::new(AtomicUsize::new(0));
+        let second_count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_fetcher(&first_count, &second_count);
+        let results = fetcher
+            .fetch_batch(&[read_params, write_params])
+            .await
+            .unwrap();
+        assert!(results.iter().all(|result| result.is_err()));
+        assert_eq!(Some(false), fetcher.is_node_healthy("node0", 20200));
+        assert_eq!(0, second_count.load(Ordering::SeqCst));
+    }
+}
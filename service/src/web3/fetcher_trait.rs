@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use futures::future::join_all;
 use serde_json::Value as JSONValue;
 
 use crate::web3::service::ServiceError;
@@ -18,4 +19,25 @@ pub fn parse_response(response: &JSONValue) -> Result<JSONValue, ServiceError> {
 #[async_trait]
 pub trait FetcherTrait {
     async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError>;
+
+    ///
+    /// 批量发送请求，返回结果的顺序与 params_list 一致，单个请求的失败不影响其他请求。
+    /// 默认实现为并发地调用 `fetch`。
+    async fn fetch_batch(
+        &self,
+        params_list: &[JSONValue],
+    ) -> Result<Vec<Result<JSONValue, ServiceError>>, ServiceError> {
+        Ok(join_all(params_list.iter().map(|params| self.fetch(params))).await)
+    }
+
+    ///
+    /// 发送交易并等待节点推送的交易回执，返回请求的结果及交易回执（未收到推送时回执为 null，
+    /// 调用方会再通过 `getTransactionReceipt` 查询）。
+    /// 不支持回执推送的 fetcher 返回 None（默认实现），此时调用方需通过 `fetch` 发送交易并轮询回执。
+    async fn fetch_with_receipt(
+        &self,
+        _params: &JSONValue,
+    ) -> Result<Option<(JSONValue, JSONValue)>, ServiceError> {
+        Ok(None)
+    }
 }
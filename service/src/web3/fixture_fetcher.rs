@@ -0,0 +1,379 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JSONValue};
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::web3::{fetcher_trait::FetcherTrait, service::ServiceError};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FixtureError {
+    pub code: i32,
+    pub message: String,
+}
+
+///
+/// 一次请求及其响应，`error` 不为空时表示节点返回了错误，否则 `result` 为请求的结果。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Fixture {
+    pub method: String,
+    pub params: JSONValue,
+    #[serde(default)]
+    pub result: JSONValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<FixtureError>,
+}
+
+impl Fixture {
+    fn to_result(&self) -> Result<JSONValue, ServiceError> {
+        match &self.error {
+            Some(error) => Err(ServiceError::FiscoBcosError {
+                code: error.code,
+                message: error.message.clone(),
+            }),
+            None => Ok(self.result.clone()),
+        }
+    }
+}
+
+fn get_method(params: &JSONValue) -> String {
+    params["method"].as_str().unwrap_or("").to_owned()
+}
+
+pub fn load_fixtures(fixture_file_path: &Path) -> Result<Vec<Fixture>, ServiceError> {
+    Ok(serde_json::from_slice(&fs::read(fixture_file_path)?)?)
+}
+
+///
+/// 对任意 fetcher 进行包装，将请求及其响应依次记录到 fixture 文件中（每次请求后整体写入）。
+/// 只记录成功的响应及节点返回的错误，网络等其他错误不会被记录。
+/// fixture 文件写入失败时，请求返回该写入错误。
+pub struct RecordingFetcher {
+    inner: Box<dyn FetcherTrait + Send + Sync>,
+    fixture_file_path: PathBuf,
+    fixtures: Mutex<Vec<Fixture>>,
+    // 保证并发请求按记录顺序写入文件，避免旧的内容覆盖新的内容。
+    write_lock: AsyncMutex<()>,
+}
+
+impl RecordingFetcher {
+    async fn record(
+        &self,
+        params: &JSONValue,
+        result: &Result<JSONValue, ServiceError>,
+    ) -> Result<(), ServiceError> {
+        let fixture = match result {
+            Ok(value) => Fixture {
+                method: get_method(params),
+                params: params["params"].clone(),
+                result: value.clone(),
+                error: None,
+            },
+            Err(ServiceError::FiscoBcosError { code, message }) => Fixture {
+                method: get_method(params),
+                params: params["params"].clone(),
+                result: json!(null),
+                error: Some(FixtureError {
+                    code: *code,
+                    message: message.clone(),
+                }),
+            },
+            Err(_) => return Ok(()),
+        };
+        let _write_guard = self.write_lock.lock().await;
+        let data = {
+            let mut fixtures = self.fixtures.lock().unwrap();
+            fixtures.push(fixture);
+            serde_json::to_vec_pretty(&*fixtures)?
+        };
+        tokio::fs::write(&self.fixture_file_path, data).await?;
+        Ok(())
+    }
+
+    pub fn new(
+        inner: Box<dyn FetcherTrait + Send + Sync>,
+        fixture_file_path: &Path,
+    ) -> RecordingFetcher {
+        RecordingFetcher {
+            inner,
+            fixture_file_path: fixture_file_path.to_path_buf(),
+            fixtures: Mutex::new(vec![]),
+            write_lock: AsyncMutex::new(()),
+        }
+    }
+
+    pub fn get_fixtures(&self) -> Vec<Fixture> {
+        self.fixtures.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl FetcherTrait for RecordingFetcher {
+    async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+        let result = self.inner.fetch(params).await;
+        self.record(params, &result).await?;
+        result
+    }
+}
+
+struct ReplayQueue {
+    // 按记录顺序排列的 fixture 下标。
+    indexes: Vec<usize>,
+    cursor: usize,
+}
+
+struct ReplayState {
+    queues: HashMap<String, ReplayQueue>,
+    // 各 fixture 是否已被返回过，重建队列时据此保留回放进度。
+    replayed: Vec<bool>,
+}
+
+///
+/// 根据 fixture 文件响应请求，请求按 method 及 params 匹配（忽略 id）。
+/// 相同的请求按记录的顺序依次返回，记录用完后重复返回最后一条；没有匹配的记录时返回错误。
+///
+/// 交易签名中包含随机的 nonce，因此 `sendRawTransaction` 及 `sendRawTransactionAndGetProof`
+/// 默认只按 method 匹配，可通过 `ignore_params` 为其他 method 设置同样的规则，
+/// 此时不同 params 的记录合并后仍按记录顺序回放，且不会重复返回已回放过的记录。
+pub struct ReplayFetcher {
+    fixtures: Vec<Fixture>,
+    state: Mutex<ReplayState>,
+    ignored_params_methods: HashSet<String>,
+}
+
+impl ReplayFetcher {
+    fn get_key(&self, method: &str, params: &JSONValue) -> String {
+        if self.ignored_params_methods.contains(method) {
+            method.to_owned()
+        } else {
+            format!("{}:{}", method, params)
+        }
+    }
+
+    ///
+    /// 按记录顺序重建队列，各队列从第一条尚未返回过的记录开始回放。
+    fn build_queues(&self, replayed: &[bool]) -> HashMap<String, ReplayQueue> {
+        let mut queues: HashMap<String, ReplayQueue> = HashMap::new();
+        for (index, fixture) in self.fixtures.iter().enumerate() {
+            let key = self.get_key(&fixture.method, &fixture.params);
+            queues
+                .entry(key)
+                .or_insert(ReplayQueue {
+                    indexes: vec![],
+                    cursor: 0,
+                })
+                .indexes
+                .push(index);
+        }
+        for queue in queues.values_mut() {
+            queue.cursor = queue
+                .indexes
+                .iter()
+                .position(|index| !replayed[*index])
+                .unwrap_or(queue.indexes.len() - 1);
+        }
+        queues
+    }
+
+    pub fn new(fixtures: Vec<Fixture>) -> ReplayFetcher {
+        let replayed = vec![false; fixtures.len()];
+        let mut fetcher = ReplayFetcher {
+            fixtures,
+            state: Mutex::new(ReplayState {
+                queues: HashMap::new(),
+                replayed: vec![],
+            }),
+            ignored_params_methods: ["sendRawTransaction", "sendRawTransactionAndGetProof"]
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+        };
+        let queues = fetcher.build_queues(&replayed);
+        fetcher.state = Mutex::new(ReplayState { queues, replayed });
+        fetcher
+    }
+
+    pub fn new_with_file(fixture_file_path: &Path) -> Result<ReplayFetcher, ServiceError> {
+        Ok(ReplayFetcher::new(load_fixtures(fixture_file_path)?))
+    }
+
+    pub fn ignore_params(mut self, method: &str) -> ReplayFetcher {
+        self.ignored_params_methods.insert(method.to_owned());
+        let replayed = std::mem::take(&mut self.state.get_mut().unwrap().replayed);
+        let queues = self.build_queues(&replayed);
+        self.state = Mutex::new(ReplayState { queues, replayed });
+        self
+    }
+}
+
+#[async_trait]
+impl FetcherTrait for ReplayFetcher {
+    async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+        let method = get_method(params);
+        let key = self.get_key(&method, &params["params"]);
+        let mut state = self.state.lock().unwrap();
+        let ReplayState { queues, replayed } = &mut *state;
+        match queues.get_mut(&key) {
+            Some(queue) => {
+                let index = queue.indexes[queue.cursor];
+                replayed[index] = true;
+                // 跳过已返回过的记录，记录用完后停留在最后一条。
+                while queue.cursor + 1 < queue.indexes.len() {
+                    queue.cursor += 1;
+                    if !replayed[queue.indexes[queue.cursor]] {
+                        break;
+                    }
+                }
+                self.fixtures[index].to_result()
+            }
+            None => Err(ServiceError::CustomError {
+                message: format!(
+                    "No fixture matched the request, method: {:?}, params: {}",
+                    method, params["params"]
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use serde_json::{json, Value as JSONValue};
+    use uuid::Uuid;
+
+    use crate::web3::fixture_fetcher::*;
+
+    struct MockFetcher {
+        count: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl FetcherTrait for MockFetcher {
+        async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst);
+            match params["method"].as_str().unwrap() {
+                "getCode" => Err(ServiceError::FiscoBcosError {
+                    code: -32602,
+                    message: String::from("Invalid params"),
+                }),
+                "sendRawTransaction" => Ok(json!(format!("0x{}", count))),
+                _ => Ok(json!(format!("0x{:x}", count + 1))),
+            }
+        }
+    }
+
+    fn generate_request_params(method: &str, params: &JSONValue) -> JSONValue {
+        json!({"id": 1, "jsonrpc": "2.0", "method": method, "params": params})
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let fixture_file_path =
+            std::env::temp_dir().join(format!("fixtures_{}.json", Uuid::new_v4()));
+        let block_number_params = generate_request_params("getBlockNumber", &json!([1]));
+        let code_params = generate_request_params("getCode", &json!([1, "0x0"]));
+        let recording_fetcher = RecordingFetcher::new(
+            Box::new(MockFetcher {
+                count: AtomicUsize::new(0),
+            }),
+            &fixture_file_path,
+        );
+        assert_eq!(
+            json!("0x1"),
+            recording_fetcher.fetch(&block_number_params).await.unwrap()
+        );
+        assert_eq!(
+            json!("0x2"),
+            recording_fetcher.fetch(&block_number_params).await.unwrap()
+        );
+        assert!(recording_fetcher.fetch(&code_params).await.is_err());
+        let transaction_params = generate_request_params("sendRawTransaction", &json!([1, "0x01"]));
+        assert_eq!(
+            json!("0x3"),
+            recording_fetcher.fetch(&transaction_params).await.unwrap()
+        );
+        assert_eq!(4, recording_fetcher.get_fixtures().len());
+
+        let replay_fetcher = ReplayFetcher::new_with_file(&fixture_file_path).unwrap();
+        fs::remove_file(&fixture_file_path).unwrap();
+        assert_eq!(
+            json!("0x1"),
+            replay_fetcher.fetch(&block_number_params).await.unwrap()
+        );
+        assert_eq!(
+            json!("0x2"),
+            replay_fetcher.fetch(&block_number_params).await.unwrap()
+        );
+        assert_eq!(
+            json!("0x2"),
+            replay_fetcher.fetch(&block_number_params).await.unwrap()
+        );
+        match replay_fetcher.fetch(&code_params).await {
+            Err(ServiceError::FiscoBcosError { code, .. }) => assert_eq!(-32602, code),
+            _ => panic!("expect FiscoBcosError"),
+        }
+        let transaction_params = generate_request_params("sendRawTransaction", &json!([1, "0x02"]));
+        assert_eq!(
+            json!("0x3"),
+            replay_fetcher.fetch(&transaction_params).await.unwrap()
+        );
+        let unmatched_params = generate_request_params("getBlockNumber", &json!([2]));
+        assert!(replay_fetcher.fetch(&unmatched_params).await.is_err());
+
+        // 忽略 params 后按记录顺序回放，已回放的记录不会重复返回。
+        let replay_fetcher = replay_fetcher.ignore_params("getBlockNumber");
+        assert_eq!(
+            json!("0x2"),
+            replay_fetcher.fetch(&unmatched_params).await.unwrap()
+        );
+
+        let recording_fetcher = RecordingFetcher::new(
+            Box::new(MockFetcher {
+                count: AtomicUsize::new(0),
+            }),
+            &std::env::temp_dir()
+                .join(format!("not_exists_{}", Uuid::new_v4()))
+                .join("fixtures.json"),
+        );
+        match recording_fetcher.fetch(&block_number_params).await {
+            Err(ServiceError::StdIOError(_)) => {}
+            _ => panic!("expect StdIOError"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_ignored_params() {
+        let fixtures: Vec<Fixture> = (0..20)
+            .map(|index| Fixture {
+                method: String::from("getBlockByNumber"),
+                params: json!([1, format!("0x{:x}", index % 7)]),
+                result: json!(index),
+                error: None,
+            })
+            .collect();
+        let replay_fetcher = ReplayFetcher::new(fixtures.clone()).ignore_params("getBlockByNumber");
+        let params = generate_request_params("getBlockByNumber", &json!([1, "0x0"]));
+        for index in 0..20 {
+            assert_eq!(json!(index), replay_fetcher.fetch(&params).await.unwrap());
+        }
+        assert_eq!(json!(19), replay_fetcher.fetch(&params).await.unwrap());
+
+        let replay_fetcher = ReplayFetcher::new(fixtures);
+        let first_params = generate_request_params("getBlockByNumber", &json!([1, "0x0"]));
+        assert_eq!(json!(0), replay_fetcher.fetch(&first_params).await.unwrap());
+        assert_eq!(json!(7), replay_fetcher.fetch(&first_params).await.unwrap());
+        let replay_fetcher = replay_fetcher.ignore_params("getBlockByNumber");
+        for index in (1..20).filter(|index| *index != 7) {
+            assert_eq!(json!(index), replay_fetcher.fetch(&params).await.unwrap());
+        }
+    }
+}
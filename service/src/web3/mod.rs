@@ -1,4 +1,10 @@
+pub mod batch_request;
+#[cfg(feature = "channel")]
 pub mod channel_fetcher;
+pub mod failover_fetcher;
 pub mod fetcher_trait;
+pub mod fixture_fetcher;
+pub mod retry_fetcher;
+#[cfg(feature = "rpc")]
 pub mod rpc_fetcher;
 pub mod service;
@@ -0,0 +1,233 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rand::Rng;
+use serde_json::Value as JSONValue;
+
+use crate::{
+    config::RetryPolicy,
+    web3::{fetcher_trait::FetcherTrait, service::ServiceError},
+};
+
+fn get_error_kind(err: &ServiceError) -> &'static str {
+    match err {
+        ServiceError::StdIOError(_) => "StdIOError",
+        ServiceError::HyperError(_) => "HyperError",
+        ServiceError::HyperHttpError(_) => "HyperHttpError",
+        ServiceError::SerdeJsonError(_) => "SerdeJsonError",
+        #[cfg(feature = "channel")]
+        ServiceError::ChannelError(_) => "ChannelError",
+        #[cfg(feature = "channel")]
+        ServiceError::TASSLError(_) => "TASSLError",
+        ServiceError::ABIError(_) => "ABIError",
+        ServiceError::AccountError(_) => "AccountError",
+        ServiceError::ConfigError(_) => "ConfigError",
+        ServiceError::TransactionError(_) => "TransactionError",
+        ServiceError::SignerError(_) => "SignerError",
+        ServiceError::CustomError { .. } => "CustomError",
+        ServiceError::FiscoBcosError { .. } => "FiscoBcosError",
+    }
+}
+
+fn is_transaction_method(params: &JSONValue) -> bool {
+    params["method"]
+        .as_str()
+        .unwrap_or("")
+        .starts_with("sendRawTransaction")
+}
+
+///
+/// 对任意 fetcher 进行包装，请求失败时按 `RetryPolicy` 进行重试，重试间隔按指数增长并附加随机抖动。
+/// 发送交易的请求（`sendRawTransaction*`）默认不会重试，以免交易被重复提交，
+/// 需设置 `retry_transactions` 为 `true` 才会重试。
+pub struct RetryFetcher {
+    inner: Box<dyn FetcherTrait + Send + Sync>,
+    policy: RetryPolicy,
+}
+
+impl RetryFetcher {
+    fn is_retryable_error(&self, err: &ServiceError) -> bool {
+        if let ServiceError::FiscoBcosError { code, .. } = err {
+            if self.policy.retryable_codes.contains(code) {
+                return true;
+            }
+        }
+        let kind = get_error_kind(err);
+        self.policy.retryable_errors.iter().any(|v| v.eq(kind))
+    }
+
+    fn is_retryable_request(&self, params_list: &[JSONValue]) -> bool {
+        self.policy.retry_transactions || !params_list.iter().any(is_transaction_method)
+    }
+
+    fn get_backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.policy.initial_backoff_milliseconds as f64
+            * self.policy.multiplier.powi(attempt as i32 - 1);
+        let backoff = backoff.min(self.policy.max_backoff_milliseconds as f64);
+        let jitter = self.policy.jitter.abs();
+        let factor = if jitter > 0.0 {
+            1.0 + rand::thread_rng().gen_range(-jitter..=jitter)
+        } else {
+            1.0
+        };
+        Duration::from_millis((backoff * factor).max(0.0) as u64)
+    }
+
+    fn get_max_attempts(&self, params_list: &[JSONValue]) -> u32 {
+        if self.is_retryable_request(params_list) {
+            self.policy.max_attempts.max(1)
+        } else {
+            1
+        }
+    }
+
+    pub fn new(inner: Box<dyn FetcherTrait + Send + Sync>, policy: &RetryPolicy) -> RetryFetcher {
+        RetryFetcher {
+            inner,
+            policy: policy.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl FetcherTrait for RetryFetcher {
+    async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+        let max_attempts = self.get_max_attempts(std::slice::from_ref(params));
+        let mut attempt = 1;
+        loop {
+            match self.inner.fetch(params).await {
+                Err(err) if attempt < max_attempts && self.is_retryable_error(&err) => {
+                    tokio::time::sleep(self.get_backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    ///
+    /// 仅在整个批量请求失败时重试，单个请求返回的错误不会触发重试。
+    async fn fetch_batch(
+        &self,
+        params_list: &[JSONValue],
+    ) -> Result<Vec<Result<JSONValue, ServiceError>>, ServiceError> {
+        let max_attempts = self.get_max_attempts(params_list);
+        let mut attempt = 1;
+        loop {
+            match self.inner.fetch_batch(params_list).await {
+                Err(err) if attempt < max_attempts && self.is_retryable_error(&err) => {
+                    tokio::time::sleep(self.get_backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    ///
+    /// 仅用于发送交易，与 `fetch` 中发送交易的请求一样默认不重试。
+    async fn fetch_with_receipt(
+        &self,
+        params: &JSONValue,
+    ) -> Result<Option<(JSONValue, JSONValue)>, ServiceError> {
+        let max_attempts = self.get_max_attempts(std::slice::from_ref(params));
+        let mut attempt = 1;
+        loop {
+            match self.inner.fetch_with_receipt(params).await {
+                Err(err) if attempt < max_attempts && self.is_retryable_error(&err) => {
+                    tokio::time::sleep(self.get_backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use serde_json::{json, Value as JSONValue};
+
+    use crate::config::RetryPolicy;
+    use crate::web3::retry_fetcher::*;
+
+    struct MockFetcher {
+        count: Arc<AtomicUsize>,
+        failures: usize,
+        code: Option<i32>,
+    }
+
+    #[async_trait]
+    impl FetcherTrait for MockFetcher {
+        async fn fetch(&self, _params: &JSONValue) -> Result<JSONValue, ServiceError> {
+            let count = self.count.fetch_add(1, Ordering::SeqCst);
+            if count >= self.failures {
+                return Ok(json!("0x1"));
+            }
+            match self.code {
+                Some(code) => Err(ServiceError::FiscoBcosError {
+                    code,
+                    message: String::from("error"),
+                }),
+                None => Err(ServiceError::StdIOError(std::io::Error::from(
+                    std::io::ErrorKind::ConnectionReset,
+                ))),
+            }
+        }
+    }
+
+    fn create_retry_fetcher(
+        count: &Arc<AtomicUsize>,
+        failures: usize,
+        code: Option<i32>,
+        policy: &RetryPolicy,
+    ) -> RetryFetcher {
+        let inner = MockFetcher {
+            count: count.clone(),
+            failures,
+            code,
+        };
+        RetryFetcher::new(Box::new(inner), policy)
+    }
+
+    #[tokio::test]
+    async fn test_retry_fetcher() {
+        let mut policy = RetryPolicy {
+            initial_backoff_milliseconds: 1,
+            ..RetryPolicy::default()
+        };
+        let read_params = json!({"method": "getBlockNumber", "params": [1]});
+        let write_params = json!({"method": "sendRawTransaction", "params": [1, "0x00"]});
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_retry_fetcher(&count, 2, None, &policy);
+        assert_eq!(json!("0x1"), fetcher.fetch(&read_params).await.unwrap());
+        assert_eq!(3, count.load(Ordering::SeqCst));
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_retry_fetcher(&count, 3, None, &policy);
+        assert!(fetcher.fetch(&read_params).await.is_err());
+        assert_eq!(3, count.load(Ordering::SeqCst));
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_retry_fetcher(&count, 1, None, &policy);
+        assert!(fetcher.fetch(&write_params).await.is_err());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_retry_fetcher(&count, 1, Some(-32603), &policy);
+        assert!(fetcher.fetch(&read_params).await.is_err());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        policy.retry_transactions = true;
+        policy.retryable_codes = vec![-32603];
+        let count = Arc::new(AtomicUsize::new(0));
+        let fetcher = create_retry_fetcher(&count, 1, Some(-32603), &policy);
+        assert_eq!(json!("0x1"), fetcher.fetch(&write_params).await.unwrap());
+        assert_eq!(2, count.load(Ordering::SeqCst));
+    }
+}
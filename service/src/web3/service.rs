@@ -1,27 +1,44 @@
 use std::{
     collections::HashMap,
+    future::Future,
     process::Command,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
 use ethabi::Token;
+use futures::future::join_all;
 use serde_json::{json, Value as JSONValue};
 use thiserror::Error;
 
+#[cfg(feature = "rpc")]
+use crate::web3::rpc_fetcher::RPCFetcher;
 use crate::{
     abi::{ABIError, ABI},
-    account::{create_account_from_pem, Account, AccountError},
-    channel::ChannelError,
-    config::Config,
+    account::AccountError,
+    account_manager::AccountManager,
+    config::{read_pem, Config, ConfigError},
     helpers::{convert_hex_str_to_u32, parse_json_string, parse_json_string_array},
-    tassl::TASSLError,
-    transaction::{get_sign_transaction_data, TransactionError},
-    web3::{channel_fetcher::ChannelFetcher, fetcher_trait::FetcherTrait, rpc_fetcher::RPCFetcher},
+    keystore::create_account_from_bytes,
+    signer::{RemoteSigner, Signer, SignerError},
+    sm_crypto::{is_sm_crypto_enabled, SM_CRYPTO_DISABLED_MESSAGE},
+    transaction::{TransactionBuilder, TransactionError},
+    web3::{
+        batch_request::BatchRequest, failover_fetcher::FailoverFetcher,
+        fetcher_trait::FetcherTrait, retry_fetcher::RetryFetcher,
+    },
 };
+#[cfg(feature = "channel")]
+use crate::{channel::ChannelError, tassl::TASSLError, web3::channel_fetcher::ChannelFetcher};
 
-fn generate_request_params(method: &str, params: &JSONValue) -> JSONValue {
+static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) fn generate_request_params(method: &str, params: &JSONValue) -> JSONValue {
     json!({
-        "id": 1,
+        "id": REQUEST_ID.fetch_add(1, Ordering::Relaxed),
         "jsonrpc": "2.0",
         "method": method.to_owned(),
         "params": params.clone(),
@@ -42,9 +59,11 @@ pub enum ServiceError {
     #[error("serde_json::Error")]
     SerdeJsonError(#[from] serde_json::Error),
 
+    #[cfg(feature = "channel")]
     #[error("channel error")]
     ChannelError(#[from] ChannelError),
 
+    #[cfg(feature = "channel")]
     #[error("tassl error")]
     TASSLError(#[from] TASSLError),
 
@@ -54,9 +73,15 @@ pub enum ServiceError {
     #[error("account error")]
     AccountError(#[from] AccountError),
 
+    #[error("config error")]
+    ConfigError(#[from] ConfigError),
+
     #[error("transaction error")]
     TransactionError(#[from] TransactionError),
 
+    #[error("signer error")]
+    SignerError(#[from] SignerError),
+
     #[error("fisco bcos custom error")]
     CustomError { message: String },
 
@@ -73,8 +98,10 @@ pub struct CallResponse {
 
 pub struct Service {
     config: Config,
-    account: Account,
-    fetcher: Box<dyn FetcherTrait + Send + Sync>,
+    signer: Arc<dyn Signer + Send + Sync>,
+    account_manager: Arc<AccountManager>,
+    transaction_builder: TransactionBuilder,
+    fetcher: Arc<dyn FetcherTrait + Send + Sync>,
 }
 
 impl Service {
@@ -86,45 +113,117 @@ impl Service {
         )?)
     }
 
-    pub(crate) async fn send_transaction_with_abi(
+    async fn get_transaction_params(
         &self,
         method: &str,
         to_address: &str,
-        abi: &ABI,
-        function_name: &str,
-        tokens: &Vec<Token>,
-    ) -> Result<String, ServiceError> {
+        data: &[u8],
+    ) -> Result<JSONValue, ServiceError> {
         let block_number = convert_hex_str_to_u32(&self.get_block_number().await?);
-        let data = abi.encode_function_input(function_name, tokens)?;
-        let transaction_data = get_sign_transaction_data(
-            &self.account,
-            self.config.group_id,
-            self.config.chain_id,
-            block_number + 500,
-            to_address,
-            &data,
-            self.config.sm_crypto,
-        )?;
-        let params = generate_request_params(
+        let transaction_builder = self
+            .transaction_builder
+            .clone()
+            .group_id(self.config.group_id)
+            .chain_id(self.config.chain_id);
+        let transaction_data = transaction_builder
+            .sign_with_signer(
+                self.signer.as_ref(),
+                transaction_builder.get_block_limit(block_number),
+                to_address,
+                data,
+                self.config.sm_crypto,
+            )
+            .await?;
+        Ok(generate_request_params(
             method,
             &json!([
                 self.config.group_id,
                 format!("0x{}", hex::encode(&transaction_data))
             ]),
-        );
+        ))
+    }
+
+    ///
+    /// 发送交易并获取交易回执，返回交易哈希及交易回执（获取超时时为 null）。
+    /// fetcher 支持回执推送（`channel` 模式）时直接等待节点推送的回执，否则轮询 `getTransactionReceipt`；
+    /// 未收到推送的回执（等待超时或连接断开）时，同样在剩余的超时时间内轮询（至少查询一次）。
+    async fn send_transaction_and_get_receipt(
+        &self,
+        params: &JSONValue,
+    ) -> Result<(String, JSONValue), ServiceError> {
+        let start = Instant::now();
+        if let Some((response, transaction_receipt)) =
+            self.fetcher.fetch_with_receipt(params).await?
+        {
+            let transaction_hash = parse_json_string(&response);
+            if !transaction_receipt.is_null() {
+                return Ok((transaction_hash, transaction_receipt));
+            }
+            let transaction_receipt = self
+                .get_transaction_receipt_with_timeout(&transaction_hash, start)
+                .await?;
+            return Ok((transaction_hash, transaction_receipt));
+        }
+        let transaction_hash = parse_json_string(&self.fetcher.fetch(params).await?);
+        let transaction_receipt = self
+            .get_transaction_receipt_with_timeout(&transaction_hash, Instant::now())
+            .await?;
+        Ok((transaction_hash, transaction_receipt))
+    }
+
+    pub(crate) async fn send_transaction_with_abi(
+        &self,
+        method: &str,
+        to_address: &str,
+        abi: &ABI,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<String, ServiceError> {
+        let data = abi.encode_function_input(function_name, tokens)?;
+        let params = self
+            .get_transaction_params(method, to_address, &data)
+            .await?;
         Ok(parse_json_string(&self.fetcher.fetch(&params).await?))
     }
 
+    pub(crate) async fn send_transaction_and_get_receipt_with_abi(
+        &self,
+        method: &str,
+        to_address: &str,
+        abi: &ABI,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<(String, JSONValue), ServiceError> {
+        let data = abi.encode_function_input(function_name, tokens)?;
+        let params = self
+            .get_transaction_params(method, to_address, &data)
+            .await?;
+        self.send_transaction_and_get_receipt(&params).await
+    }
+
     pub(crate) async fn call_with_abi(
         &self,
         to_address: &str,
         abi: &ABI,
         function_name: &str,
         tokens: &Vec<Token>,
+    ) -> Result<CallResponse, ServiceError> {
+        let from = format!("0x{}", hex::encode(self.signer.address()));
+        self.call_from_with_abi(&from, to_address, abi, function_name, tokens)
+            .await
+    }
+
+    async fn call_from_with_abi(
+        &self,
+        from: &str,
+        to_address: &str,
+        abi: &ABI,
+        function_name: &str,
+        tokens: &Vec<Token>,
     ) -> Result<CallResponse, ServiceError> {
         let transaction_data = abi.encode_function_input(function_name, tokens)?;
         let params = json!({
-            "from": format!("0x{}", hex::encode(&self.account.address)),
+            "from": from.to_owned(),
             "to": to_address.to_owned(),
             "value": "0x0",
             "data": format!("0x{}", hex::encode(&transaction_data)),
@@ -143,33 +242,70 @@ impl Service {
         })
     }
 
+    ///
+    /// 自 start 起在 `timeout_seconds` 内轮询交易回执，至少查询一次，超时返回 null。
     pub(crate) async fn get_transaction_receipt_with_timeout(
         &self,
         transaction_hash: &str,
+        start: Instant,
     ) -> Result<JSONValue, ServiceError> {
-        let start = Instant::now();
-        let timeout_milliseconds = (1000 * self.config.timeout_seconds) as u128;
-        while Instant::now().duration_since(start).as_millis() < timeout_milliseconds {
+        let timeout_milliseconds = (1000 * self.config.timeout_seconds.max(0)) as u128;
+        loop {
             let transaction_receipt: JSONValue =
                 self.get_transaction_receipt(transaction_hash).await?;
-            if transaction_receipt.is_null() {
-                tokio::time::sleep(Duration::from_millis(200)).await;
-                continue;
+            if !transaction_receipt.is_null()
+                || Instant::now().duration_since(start).as_millis() >= timeout_milliseconds
+            {
+                return Ok(transaction_receipt);
             }
-            let transaction_receipt = self.get_transaction_receipt(transaction_hash).await?;
-            return Ok(transaction_receipt);
+            tokio::time::sleep(Duration::from_millis(200)).await;
         }
-        Ok(json!(null))
     }
 
     pub fn new(
         config: &Config,
         fetcher: Box<dyn FetcherTrait + Send + Sync>,
     ) -> Result<Service, ServiceError> {
+        if config.sm_crypto && !is_sm_crypto_enabled() {
+            return Err(ServiceError::CustomError {
+                message: SM_CRYPTO_DISABLED_MESSAGE.to_owned(),
+            });
+        }
+        let signer: Arc<dyn Signer + Send + Sync> = match (&config.signer, &config.remote_signer) {
+            (Some(signer), _) => signer.clone(),
+            (None, Some(remote_signer)) => {
+                let public_key = hex::decode(remote_signer.public_key.trim_start_matches("0x"))
+                    .map_err(AccountError::from)?;
+                let signer = RemoteSigner::new(&remote_signer.url, &public_key, config.sm_crypto)?;
+                Arc::new(match &remote_signer.bearer_token {
+                    Some(bearer_token) => signer.bearer_token(bearer_token),
+                    None => signer,
+                })
+            }
+            (None, None) => {
+                let account_file =
+                    read_pem(&config.account_pem, &config.account).map_err(AccountError::from)?;
+                Arc::new(create_account_from_bytes(
+                    &account_file,
+                    config.account_password.as_deref(),
+                    config.sm_crypto,
+                )?)
+            }
+        };
+        let account_manager = AccountManager::new();
+        if let Some(accounts_directory) = &config.accounts_directory {
+            account_manager.load_directory(
+                accounts_directory,
+                config.account_password.as_deref(),
+                config.sm_crypto,
+            )?;
+        }
         Ok(Service {
-            fetcher,
+            fetcher: Arc::from(fetcher),
             config: config.clone(),
-            account: create_account_from_pem(&config.account, config.sm_crypto)?,
+            signer,
+            account_manager: Arc::new(account_manager),
+            transaction_builder: TransactionBuilder::default(),
         })
     }
 
@@ -177,6 +313,111 @@ impl Service {
         self.config.clone()
     }
 
+    pub fn get_signer(&self) -> Arc<dyn Signer + Send + Sync> {
+        self.signer.clone()
+    }
+
+    ///
+    /// 账户管理器，与 `group`、`with_signer` 及 `as_account` 返回的服务实例共享。
+    pub fn get_account_manager(&self) -> Arc<AccountManager> {
+        self.account_manager.clone()
+    }
+
+    ///
+    /// 返回以 group_id 为组 ID 的服务实例，与当前实例共享 fetcher（及其连接）和签名者。
+    pub fn group(&self, group_id: u32) -> Service {
+        let mut config = self.config.clone();
+        config.group_id = group_id;
+        Service {
+            config,
+            signer: self.signer.clone(),
+            account_manager: self.account_manager.clone(),
+            transaction_builder: self.transaction_builder.clone(),
+            fetcher: self.fetcher.clone(),
+        }
+    }
+
+    ///
+    /// 返回使用 signer 签名的服务实例，与当前实例共享 fetcher（及其连接）。
+    pub fn with_signer(&self, signer: Arc<dyn Signer + Send + Sync>) -> Service {
+        let mut config = self.config.clone();
+        config.signer = Some(signer.clone());
+        Service {
+            config,
+            signer,
+            account_manager: self.account_manager.clone(),
+            transaction_builder: self.transaction_builder.clone(),
+            fetcher: self.fetcher.clone(),
+        }
+    }
+
+    ///
+    /// 返回使用 transaction_builder 构造交易的服务实例，与当前实例共享 fetcher（及其连接）、签名者和账户管理器。
+    /// 交易中的 group id 及 chain id 仍使用配置中的值。
+    pub fn with_transaction_builder(&self, transaction_builder: TransactionBuilder) -> Service {
+        Service {
+            config: self.config.clone(),
+            signer: self.signer.clone(),
+            account_manager: self.account_manager.clone(),
+            transaction_builder,
+            fetcher: self.fetcher.clone(),
+        }
+    }
+
+    ///
+    /// 返回使用账户管理器中别名为 alias 的签名者签名的服务实例，与当前实例共享 fetcher（及其连接）。
+    pub fn as_account(&self, alias: &str) -> Result<Service, ServiceError> {
+        match self.account_manager.get(alias) {
+            Some(signer) => Ok(self.with_signer(signer)),
+            None => Err(ServiceError::CustomError {
+                message: format!("Account {:?} is not registered", alias),
+            }),
+        }
+    }
+
+    ///
+    /// 将 `get_group_list` 返回的组 ID 转换为数字。
+    pub async fn get_group_ids(&self) -> Result<Vec<u32>, ServiceError> {
+        self.get_group_list()
+            .await?
+            .iter()
+            .map(|group_id| {
+                group_id
+                    .parse::<u32>()
+                    .map_err(|_| ServiceError::CustomError {
+                        message: format!("Invalid group id: {:?}", group_id),
+                    })
+            })
+            .collect()
+    }
+
+    ///
+    /// 对 `get_group_list` 返回的每个组并发地执行 query（参数为该组的服务实例，参见 `group`），
+    /// 返回组 ID 及对应的结果，单个组的失败不影响其他组。
+    pub async fn query_all_groups<F, Fut, T>(
+        &self,
+        query: F,
+    ) -> Result<Vec<(u32, Result<T, ServiceError>)>, ServiceError>
+    where
+        F: Fn(Service) -> Fut,
+        Fut: Future<Output = Result<T, ServiceError>>,
+    {
+        let group_ids = self.get_group_ids().await?;
+        let results = join_all(
+            group_ids
+                .iter()
+                .map(|group_id| query(self.group(*group_id))),
+        )
+        .await;
+        Ok(group_ids.into_iter().zip(results).collect())
+    }
+
+    ///
+    /// 创建批量请求，加入的请求会在调用 `send` 时一次性发出。
+    pub fn batch(&self) -> BatchRequest<'_> {
+        BatchRequest::new(self.fetcher.as_ref(), self.config.group_id)
+    }
+
     pub async fn get_client_version(&self) -> Result<JSONValue, ServiceError> {
         let params = generate_request_params("getClientVersion", &json!([self.config.group_id]));
         Ok(self.fetcher.fetch(&params).await?)
@@ -390,6 +631,21 @@ impl Service {
             .await?)
     }
 
+    ///
+    /// 以 from 为调用者地址调用合约，from 无需在账户管理器中注册。
+    pub async fn call_from(
+        &self,
+        from: &str,
+        contract_name: &str,
+        to_address: &str,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<CallResponse, ServiceError> {
+        let abi = self.get_abi(contract_name)?;
+        self.call_from_with_abi(from, to_address, &abi, function_name, tokens)
+            .await
+    }
+
     pub async fn send_raw_transaction(
         &self,
         contract_name: &str,
@@ -409,6 +665,21 @@ impl Service {
             .await?)
     }
 
+    ///
+    /// 使用账户管理器中别名为 alias 的签名者发送交易。
+    pub async fn send_raw_transaction_as(
+        &self,
+        alias: &str,
+        contract_name: &str,
+        to_address: &str,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<String, ServiceError> {
+        self.as_account(alias)?
+            .send_raw_transaction(contract_name, to_address, function_name, tokens)
+            .await
+    }
+
     pub async fn send_raw_transaction_and_get_proof(
         &self,
         contract_name: &str,
@@ -428,34 +699,65 @@ impl Service {
             .await?)
     }
 
+    ///
+    /// 发送交易并等待交易回执，`channel` 模式下直接等待节点推送的回执，`rpc` 模式下轮询回执。
+    pub async fn send_raw_transaction_and_get_receipt(
+        &self,
+        contract_name: &str,
+        to_address: &str,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<JSONValue, ServiceError> {
+        let abi = self.get_abi(contract_name)?;
+        let (transaction_hash, transaction_receipt) = self
+            .send_transaction_and_get_receipt_with_abi(
+                "sendRawTransaction",
+                to_address,
+                &abi,
+                function_name,
+                tokens,
+            )
+            .await?;
+        if transaction_receipt.is_null() {
+            Err(ServiceError::CustomError {
+                message: format!(
+                    "Transaction sent, but the action for fetching transaction receipt is \
+                     timeout. Transaction hash is {:?}",
+                    transaction_hash
+                ),
+            })
+        } else {
+            Ok(transaction_receipt)
+        }
+    }
+
+    ///
+    /// 使用账户管理器中别名为 alias 的签名者发送交易并等待交易回执。
+    pub async fn send_raw_transaction_and_get_receipt_as(
+        &self,
+        alias: &str,
+        contract_name: &str,
+        to_address: &str,
+        function_name: &str,
+        tokens: &Vec<Token>,
+    ) -> Result<JSONValue, ServiceError> {
+        self.as_account(alias)?
+            .send_raw_transaction_and_get_receipt(contract_name, to_address, function_name, tokens)
+            .await
+    }
+
     pub async fn deploy(
         &self,
         contract_name: &str,
         tokens: &Vec<Token>,
     ) -> Result<JSONValue, ServiceError> {
-        let block_number = convert_hex_str_to_u32(&self.get_block_number().await?);
         let abi = self.get_abi(contract_name)?;
         let data = abi.encode_constructor_input(tokens)?;
-        let transaction_data = get_sign_transaction_data(
-            &self.account,
-            self.config.group_id,
-            self.config.chain_id,
-            block_number + 500,
-            "",
-            &data,
-            self.config.sm_crypto,
-        )?;
-        let params = generate_request_params(
-            "sendRawTransactionAndGetProof",
-            &json!([
-                self.config.group_id,
-                format!("0x{}", hex::encode(&transaction_data))
-            ]),
-        );
-        let transaction_hash = parse_json_string(&self.fetcher.fetch(&params).await?);
-        let transaction_receipt = self
-            .get_transaction_receipt_with_timeout(&transaction_hash)
+        let params = self
+            .get_transaction_params("sendRawTransactionAndGetProof", "", &data)
             .await?;
+        let (transaction_hash, transaction_receipt) =
+            self.send_transaction_and_get_receipt(&params).await?;
         if transaction_receipt.is_null() {
             Err(ServiceError::CustomError {
                 message: format!(
@@ -628,12 +930,185 @@ impl Service {
     }
 }
 
+///
+/// 根据 `service_type` 创建单节点的 fetcher，对应的 feature（`rpc` 或 `channel`）未启用时返回错误。
+pub(crate) fn create_node_fetcher(
+    config: &Config,
+) -> Result<Box<dyn FetcherTrait + Send + Sync>, ServiceError> {
+    match config.service_type.as_str() {
+        #[cfg(feature = "rpc")]
+        "rpc" => Ok(Box::new(RPCFetcher::new_with_config(
+            &config.node.host,
+            config.node.port,
+            &config.rpc,
+        )?)),
+        #[cfg(feature = "channel")]
+        "channel" => Ok(Box::new(ChannelFetcher::new(config))),
+        service_type => Err(ServiceError::CustomError {
+            message: format!(
+                "Unsupported service type: {:?}, please check the enabled features",
+                service_type
+            ),
+        }),
+    }
+}
+
 pub fn create_service_with_config(config: &Config) -> Result<Service, ServiceError> {
-    if config.service_type.eq("rpc") {
-        let fetcher = RPCFetcher::new(&config.node.host, config.node.port);
-        Service::new(&config, Box::new(fetcher))
+    let fetcher: Box<dyn FetcherTrait + Send + Sync> = if !config.nodes.is_empty() {
+        Box::new(FailoverFetcher::new(config)?)
     } else {
-        let fetcher = ChannelFetcher::new(&config);
-        Service::new(&config, Box::new(fetcher))
+        create_node_fetcher(config)?
+    };
+    match &config.retry {
+        Some(retry_policy) => {
+            Service::new(config, Box::new(RetryFetcher::new(fetcher, retry_policy)))
+        }
+        None => Service::new(config, fetcher),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use serde_json::{json, Value as JSONValue};
+
+    use crate::account::generate_account;
+    use crate::config::ConfigBuilder;
+    #[cfg(feature = "channel")]
+    use crate::testing::mock_node::{MockNode, MockTransactionResult};
+    use crate::web3::{fetcher_trait::FetcherTrait, service::*};
+
+    struct GroupFetcher;
+
+    #[async_trait]
+    impl FetcherTrait for GroupFetcher {
+        async fn fetch(&self, params: &JSONValue) -> Result<JSONValue, ServiceError> {
+            match params["method"].as_str().unwrap() {
+                "getGroupList" => Ok(json!(["1", "2", "3"])),
+                _ => Ok(json!(format!(
+                    "0x{:x}",
+                    params["params"][0].as_u64().unwrap()
+                ))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_group() {
+        let private_key = hex::decode(
+            "308184020100301006072a8648ce3d020106052b8104000a046d306b0201010420\
+             0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let account_pem = pem::encode(&pem::Pem {
+            tag: "PRIVATE KEY".to_owned(),
+            contents: private_key,
+        });
+        let config = ConfigBuilder::new("rpc")
+            .account_pem(account_pem.as_bytes())
+            .build()
+            .unwrap();
+        let service = Service::new(&config, Box::new(GroupFetcher)).unwrap();
+        assert_eq!("0x1", service.get_block_number().await.unwrap());
+
+        let group_service = service.group(2);
+        assert_eq!(2, group_service.get_config().group_id);
+        assert_eq!("0x2", group_service.get_block_number().await.unwrap());
+        assert_eq!(group_service.signer.address(), service.signer.address());
+
+        let bob = generate_account(false).unwrap();
+        let bob_address = bob.address.clone();
+        service.get_account_manager().register("bob", Arc::new(bob));
+        let bob_service = group_service.as_account("bob").unwrap();
+        assert_eq!(bob_address, bob_service.signer.address());
+        assert_eq!(2, bob_service.get_config().group_id);
+        assert_eq!("0x2", bob_service.get_block_number().await.unwrap());
+        assert!(service.as_account("carol").is_err());
+
+        let results = service
+            .query_all_groups(|group_service| async move { group_service.get_block_number().await })
+            .await
+            .unwrap();
+        let results: Vec<(u32, String)> = results
+            .into_iter()
+            .map(|(group_id, result)| (group_id, result.unwrap()))
+            .collect();
+        assert_eq!(
+            vec![
+                (1, String::from("0x1")),
+                (2, String::from("0x2")),
+                (3, String::from("0x3")),
+            ],
+            results
+        );
+    }
+
+    #[cfg(feature = "channel")]
+    #[tokio::test]
+    async fn test_send_transaction_and_get_receipt_over_channel() {
+        let node = MockNode::start(1, 1, false).await.unwrap();
+        node.set_transaction_handler(|_| MockTransactionResult {
+            status: String::from("0x0"),
+            output: String::from("0x01"),
+        });
+        let account = generate_account(false).unwrap();
+        let address = account.get_address();
+        let config = Config {
+            service_type: String::from("channel"),
+            signer: Some(Arc::new(account)),
+            ..Config::default()
+        };
+        let fetcher = node.create_channel_fetcher(&config).await.unwrap();
+        let service = Service::new(&config, Box::new(fetcher)).unwrap();
+        let params = service
+            .get_transaction_params(
+                "sendRawTransaction",
+                "0x0000000000000000000000000000000000001000",
+                &[1, 2],
+            )
+            .await
+            .unwrap();
+        let (transaction_hash, transaction_receipt) = service
+            .send_transaction_and_get_receipt(&params)
+            .await
+            .unwrap();
+        assert_eq!(
+            json!(transaction_hash),
+            transaction_receipt["transactionHash"]
+        );
+        assert_eq!(json!(address), transaction_receipt["from"]);
+        assert_eq!(json!("0x01"), transaction_receipt["output"]);
+        assert_eq!(1, node.get_method_call_count("sendRawTransaction"));
+        // 回执由节点推送（TransactionNotify），不会轮询 getTransactionReceipt。
+        assert_eq!(0, node.get_method_call_count("getTransactionReceipt"));
+
+        // 未收到推送的回执时，通过 getTransactionReceipt 查询回执。
+        node.set_transaction_notify_enabled(false);
+        let config = Config {
+            timeout_seconds: 1,
+            ..config
+        };
+        let service = Service::new(
+            &config,
+            Box::new(node.create_channel_fetcher(&config).await.unwrap()),
+        )
+        .unwrap();
+        let params = service
+            .get_transaction_params(
+                "sendRawTransaction",
+                "0x0000000000000000000000000000000000001000",
+                &[3, 4],
+            )
+            .await
+            .unwrap();
+        let (transaction_hash, transaction_receipt) = service
+            .send_transaction_and_get_receipt(&params)
+            .await
+            .unwrap();
+        assert_eq!(
+            json!(transaction_hash),
+            transaction_receipt["transactionHash"]
+        );
+        assert_eq!(1, node.get_method_call_count("getTransactionReceipt"));
     }
 }